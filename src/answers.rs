@@ -0,0 +1,96 @@
+use std::{error, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::profile::DATA_DIR;
+
+/// Where the checked-in expected answers live.
+pub const ANSWERS_PATH: &str = "./data/answers.json";
+
+/// The known-good answers for one day run against one input file. A part left
+/// out of the file is not checked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Expected {
+    pub day: usize,
+    /// Input file name, relative to the `data` directory.
+    pub input: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+    /// The directory `input` is relative to, which is the data directory
+    /// unless the answers came from a profile somewhere else.
+    #[serde(skip, default = "data_dir")]
+    pub dir: PathBuf,
+}
+
+fn data_dir() -> PathBuf {
+    PathBuf::from(DATA_DIR)
+}
+
+impl Expected {
+    pub fn input_path(&self) -> PathBuf {
+        self.dir.join(&self.input)
+    }
+
+    pub fn part(&self, part: usize) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+pub fn load(path: &str) -> Result<Vec<Expected>, Box<dyn error::Error>> {
+    let contents = fs::read_to_string(path)?;
+    let answers: Vec<Expected> = serde_json::from_str(&contents)
+        .map_err(|e| format!("Couldn't parse answers file {}: {}", path, e))?;
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    #[test]
+    fn parts_left_out_are_not_checked() {
+        let expected: Expected =
+            serde_json::from_str(r#"{ "day": 10, "input": "day10.txt", "part1": "13140" }"#)
+                .unwrap();
+        assert_eq!(expected.part(1), Some("13140"));
+        assert_eq!(expected.part(2), None);
+        assert_eq!(expected.part(3), None);
+        assert_eq!(
+            expected.input_path(),
+            PathBuf::from(DATA_DIR).join("day10.txt")
+        );
+
+        // Nor written back out, and neither is the directory
+        assert_eq!(
+            serde_json::to_string(&expected).unwrap(),
+            r#"{"day":10,"input":"day10.txt","part1":"13140"}"#
+        );
+    }
+
+    #[test]
+    fn loading() {
+        let answers = load(ANSWERS_PATH).unwrap();
+        assert!(answers.iter().any(|e| e.day == 1 && e.input == "day1.txt"));
+
+        let path = env::temp_dir().join(format!("aoc-answers-{}.json", process::id()));
+        fs::write(&path, r#"[{ "day": "one" }]"#).unwrap();
+        let path = path.display().to_string();
+        let error = load(&path).unwrap_err().to_string();
+        fs::remove_file(&path).unwrap();
+        assert!(
+            error.starts_with(&format!("Couldn't parse answers file {}", path)),
+            "{}",
+            error
+        );
+
+        assert!(load("./data/no-such-answers.json").is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    error, fs,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{solution::AnySolution, verbose};
+
+/// Summary of how long one phase (parse, part 1 or part 2) took across all
+/// the repeated runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub median_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+}
+
+impl PhaseTiming {
+    fn from_samples(mut samples: Vec<Duration>) -> PhaseTiming {
+        samples.sort();
+        let nanos = |d: &Duration| d.as_nanos() as u64;
+        let mid = samples.len() / 2;
+        let median_ns = if samples.len().is_multiple_of(2) {
+            (nanos(&samples[mid - 1]) + nanos(&samples[mid])) / 2
+        } else {
+            nanos(&samples[mid])
+        };
+        PhaseTiming {
+            median_ns,
+            min_ns: nanos(samples.first().unwrap()),
+            max_ns: nanos(samples.last().unwrap()),
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayBench {
+    pub day: usize,
+    pub input: String,
+    pub parse: PhaseTiming,
+    pub part1: PhaseTiming,
+    pub part2: PhaseTiming,
+}
+
+impl DayBench {
+    pub fn total(&self) -> Duration {
+        self.parse.median() + self.part1.median() + self.part2.median()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub runs: usize,
+    pub days: Vec<DayBench>,
+}
+
+impl BenchReport {
+    pub fn load(path: &str) -> Result<BenchReport, Box<dyn error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)
+            .map_err(|e| format!("Couldn't parse bench report {}: {}", path, e))?)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn error::Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    fn find(&self, day: usize) -> Option<&DayBench> {
+        self.days.iter().find(|d| d.day == day)
+    }
+}
+
+fn timed<T>(
+    f: impl FnOnce() -> Result<T, Box<dyn error::Error>>,
+) -> Result<(T, Duration), Box<dyn error::Error>> {
+    let start = Instant::now();
+    let result = f()?;
+    Ok((result, start.elapsed()))
+}
+
+/// Parses and solves `input` `runs` times from scratch, timing each phase
+/// separately.
+pub fn bench_day(
+    solution: &dyn AnySolution,
+    input_name: &str,
+    input: &str,
+    runs: usize,
+) -> Result<DayBench, Box<dyn error::Error>> {
+    if runs == 0 {
+        return Err("Need at least one run".into());
+    }
+
+    let mut parse = Vec::with_capacity(runs);
+    let mut part1 = Vec::with_capacity(runs);
+    let mut part2 = Vec::with_capacity(runs);
+
+    for run in 1..=runs {
+        verbose!("Day {} run {} of {}", solution.day(), run, runs);
+        let (parsed, elapsed) =
+            timed(|| Ok(solution.parse(input).map_err(|e| e.in_file(input_name))?))?;
+        parse.push(elapsed);
+        part1.push(timed(|| solution.part1(&*parsed))?.1);
+        part2.push(timed(|| solution.part2(&*parsed))?.1);
+    }
+
+    Ok(DayBench {
+        day: solution.day(),
+        input: input_name.to_string(),
+        parse: PhaseTiming::from_samples(parse),
+        part1: PhaseTiming::from_samples(part1),
+        part2: PhaseTiming::from_samples(part2),
+    })
+}
+
+pub(crate) fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+/// Percentage change of `now` relative to `before`, e.g. "+12%".
+fn format_change(before: Duration, now: Duration) -> String {
+    if before.is_zero() {
+        return "-".to_string();
+    }
+    let change = (now.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+    format!("{:+.0}%", change)
+}
+
+/// Prints the median of each phase per day. Given an earlier report, also
+/// shows how each day's total changed since then.
+pub fn print_table(report: &BenchReport, baseline: Option<&BenchReport>) {
+    print!(
+        "{:>3}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "day", "parse", "part 1", "part 2", "total"
+    );
+    if baseline.is_some() {
+        print!("  {:>10}  {:>7}", "before", "change");
+    }
+    println!();
+
+    for day in &report.days {
+        print!(
+            "{:>3}  {:>10}  {:>10}  {:>10}  {:>10}",
+            day.day,
+            format_duration(day.parse.median()),
+            format_duration(day.part1.median()),
+            format_duration(day.part2.median()),
+            format_duration(day.total()),
+        );
+        if let Some(baseline) = baseline {
+            match baseline.find(day.day) {
+                Some(before) => print!(
+                    "  {:>10}  {:>7}",
+                    format_duration(before.total()),
+                    format_change(before.total(), day.total())
+                ),
+                None => print!("  {:>10}  {:>7}", "-", "-"),
+            }
+        }
+        println!();
+    }
+
+    let total: Duration = report.days.iter().map(|d| d.total()).sum();
+    println!();
+    println!(
+        "Medians of {} runs; {} in total",
+        report.runs,
+        format_duration(total)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    fn ns(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|&n| Duration::from_nanos(n)).collect()
+    }
+
+    #[test]
+    fn durations_in_sensible_units() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(1_000)), "1.0µs");
+        assert_eq!(format_duration(Duration::from_nanos(123_456)), "123.5µs");
+        assert_eq!(format_duration(Duration::from_micros(2_500)), "2.5ms");
+        assert_eq!(format_duration(Duration::from_millis(1_234)), "1.23s");
+        assert_eq!(format_duration(Duration::from_secs(90)), "90.00s");
+    }
+
+    #[test]
+    fn medians_and_ranges() {
+        let odd = PhaseTiming::from_samples(ns(&[30, 10, 20]));
+        assert_eq!(
+            odd,
+            PhaseTiming {
+                median_ns: 20,
+                min_ns: 10,
+                max_ns: 30
+            }
+        );
+        let even = PhaseTiming::from_samples(ns(&[40, 10, 20, 35]));
+        assert_eq!((even.median_ns, even.min_ns, even.max_ns), (27, 10, 40));
+        assert_eq!(
+            PhaseTiming::from_samples(ns(&[5])).median(),
+            Duration::from_nanos(5)
+        );
+    }
+
+    #[test]
+    fn changes_since_a_baseline() {
+        let ms = Duration::from_millis;
+        assert_eq!(format_change(ms(100), ms(112)), "+12%");
+        assert_eq!(format_change(ms(100), ms(50)), "-50%");
+        assert_eq!(format_change(ms(100), ms(100)), "+0%");
+        assert_eq!(format_change(Duration::ZERO, ms(1)), "-");
+    }
+
+    #[test]
+    fn benching_a_day() {
+        let input = fs::read_to_string("./data/day1-sample.txt").unwrap();
+        let solution = days::get(1).unwrap();
+        let bench = bench_day(solution, "day1-sample.txt", &input, 3).unwrap();
+        assert_eq!((bench.day, bench.input.as_str()), (1, "day1-sample.txt"));
+        for phase in &[bench.parse, bench.part1, bench.part2] {
+            assert!(phase.min_ns <= phase.median_ns && phase.median_ns <= phase.max_ns);
+        }
+        assert_eq!(
+            bench.total(),
+            bench.parse.median() + bench.part1.median() + bench.part2.median()
+        );
+
+        assert!(bench_day(solution, "day1-sample.txt", &input, 0).is_err());
+        let error = bench_day(solution, "bad.txt", "x\n", 1).unwrap_err();
+        assert!(error.to_string().starts_with("bad.txt:1:"), "{}", error);
+    }
+}
@@ -0,0 +1,777 @@
+use std::{env, error, fs, io, path::Path, process, time::Duration};
+
+use serde::Serialize;
+
+use aoc2022::{
+    answers,
+    bench::{self, BenchReport},
+    checked, days,
+    diagnostics::Diagnostics,
+    differential,
+    error::InputError,
+    export::{self, ExportOptions},
+    fuzz, html, info, input,
+    logging::{self, Level},
+    params::{self, Params, ParamsEntry},
+    profile::{Profile, PROFILE_VAR},
+    report, run_all,
+    solution::AnySolution,
+    verbose,
+    verify::{self, VerifyReport},
+    viewer::{self, Viewer},
+};
+
+const USAGE: &str = "Usage: aoc --day N [--part 1|2] [--input PATH|-] [OUTPUT]
+       aoc --verify [--day N] [--part 1|2] [--answers PATH | --all-profiles] [OUTPUT]
+       aoc --bench [--day N] [--input PATH|-] [--runs N] [--save PATH] [--baseline PATH]
+                   [OUTPUT]
+       aoc --differential [--day N] [--cases N] [--seed N] [OUTPUT]
+       aoc --fuzz [--day N] [--cases N] [--seed N] [OUTPUT]
+       aoc --all [--timeout SECS] [OUTPUT]
+       aoc --view --day N [--part 1|2] [--input PATH]
+       aoc --export DIR --day N [--part 1|2] [--input PATH] [--image-format ppm|pbm|svg]
+                   [--scale N] [--steps N] [--every N] [OUTPUT]
+       aoc --html PATH [--day N] [--steps N] [OUTPUT]
+       aoc --watch --day N [--part 1|2] [--input PATH] [--samples] [-q|-v]
+where OUTPUT is [--format text|json] [-q|-v|-vv]
+and every mode that solves takes [--profile NAME] [--params PATH]
+[--param NAME=VALUE]... [--checked]
+
+Runs the solver for day N and prints its answers. The input defaults to
+./data/dayN.txt; pass - to read it from stdin.
+
+Everyone's puzzle inputs are different. To keep more than one set, put each
+in its own profile directory, as ./data/NAME/dayN.txt with the answers for
+them in ./data/NAME/answers.json, and choose it with --profile NAME or by
+setting AOC_PROFILE. The worked examples stay in ./data for everyone.
+
+With --verify, checks every day (or just day N) against the expected answers
+in the profile's answers.json (or --answers PATH) and prints a pass/fail
+table. --all-profiles checks the default profile and every named one.
+
+With --bench, times parsing and each part separately for every day (or just
+day N), repeating --runs times (default 5) and printing the medians. --save
+writes the timings as JSON; --baseline compares against a previously saved
+file.
+
+With --differential, checks the days with clever shortcuts against slow
+reference solvers on --cases random inputs each (default 1000), generated
+from --seed (default 0). A mismatch is shrunk to the smallest input that
+still shows it.
+
+With --fuzz, feeds every day's parser (or just day N's) --cases random
+mutations of its sample input (default 10000), generated from --seed (default
+0). Malformed input should always come back as an error; an input that makes
+a parser panic is shrunk and printed.
+
+With --all, solves both parts of every day at once on a thread pool and
+prints a table of the answers and how long each took. A day still running
+after --timeout seconds (default 600) is given up on; like a day that panics,
+it is reported without stopping the others.
+
+With --view, steps through day N's simulation in the terminal, for the days
+that have one (9, 14, 17, 22 and 23): forward and back a step at a time, to a
+given step, or on to the next step whose status line contains some text.
+Large maps can be zoomed out and panned around. The keys are listed at the
+bottom of the screen.
+
+With --export, draws day N in DIR as dayN-partP.ppm, or as a numbered
+sequence of frames from dayN-partP-00000.ppm for a simulation or a stack of
+slices. Days 8, 12 and 18 draw pictures of their answers; days 9, 14, 17, 22
+and 23 are animated for up to --steps steps (default 1000), writing every
+--every'th frame (default 1) and the last. --image-format pbm writes black
+and white bitmaps and svg writes vector drawings; --scale (default 4) is how
+many pixels wide each cell is.
+
+With --html, solves every day (or just day N) and writes a single HTML page
+to PATH with the answers, how long parsing and each part took, what the days
+report as they go, such as the cycles they find, and the days' pictures. The
+simulations are shown at up to five points in their first --steps steps
+(default 1000). Everything is inline, so the page needs nothing else to view.
+--format json also prints the reports the page was made from.
+
+With --watch, solves day N, then solves it again whenever its input or any
+of the source files changes, showing each answer next to the one before it.
+--samples also runs it on the worked example, ./data/dayN-sample.txt. The
+solver is rebuilt with cargo each time, so compile errors show up as they
+happen. This needs Linux, as it uses inotify to see the changes.
+
+Puzzle constants that differ between inputs, such as day 15's search area,
+are read from ./data/params.json (or --params PATH), keyed by day and input
+file name; anything not in there takes the value for the real inputs.
+--param NAME=VALUE overrides one for the day given by --day, and can be
+repeated.
+
+--checked makes the days whose numbers grow fastest (11, 20, 21 and 25)
+check their arithmetic, so that a result too big for its type, or a division
+by zero, is reported as an error naming the day and the calculation instead
+of silently wrapping around in a release build.
+
+--format json prints a single JSON document instead of text. When solving, it
+includes how long each phase took and any diagnostics the day reports, such as
+cycles found or intermediate counts; in text mode those go to stderr.
+
+Anything besides the answers goes to stderr. -q leaves out everything but
+errors, -v adds timings and other detail, and -vv adds progress from inside
+the slower days.";
+
+const DEFAULT_BENCH_RUNS: usize = 5;
+const DEFAULT_DIFFERENTIAL_CASES: usize = 1000;
+const DEFAULT_FUZZ_CASES: usize = 10000;
+const DEFAULT_TIMEOUT_SECS: u64 = 600;
+const DEFAULT_EXPORT_SCALE: usize = 4;
+const DEFAULT_EXPORT_STEPS: usize = 1000;
+/// The size of the largest inputs the differential checks generate.
+const DIFFERENTIAL_MAX_SIZE: usize = 10;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Solve,
+    Verify,
+    Bench,
+    Differential,
+    Fuzz,
+    All,
+    View,
+    Export,
+    Html,
+    Watch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug)]
+struct Args {
+    mode: Mode,
+    format: Format,
+    level: Level,
+    day: Option<usize>,
+    part: Option<usize>,
+    input: Option<String>,
+    answers: Option<String>,
+    runs: usize,
+    save: Option<String>,
+    baseline: Option<String>,
+    cases: usize,
+    seed: u64,
+    timeout: Duration,
+    params: Option<String>,
+    overrides: Params,
+    profile: Profile,
+    all_profiles: bool,
+    export_dir: Option<String>,
+    export: ExportOptions,
+    html_path: Option<String>,
+    steps: usize,
+    checked: bool,
+    samples: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, Box<dyn error::Error>> {
+    let mut day: Option<usize> = None;
+    let mut part: Option<usize> = None;
+    let mut input: Option<String> = None;
+    let mut answers: Option<String> = None;
+    let mut runs = DEFAULT_BENCH_RUNS;
+    let mut save: Option<String> = None;
+    let mut baseline: Option<String> = None;
+    let mut cases: Option<usize> = None;
+    let mut seed: u64 = 0;
+    let mut timeout_secs = DEFAULT_TIMEOUT_SECS;
+    let mut params: Option<String> = None;
+    let mut overrides = Params::new();
+    let mut profile_name: Option<String> = None;
+    let mut all_profiles = false;
+    let mut export_dir: Option<String> = None;
+    let mut html_path: Option<String> = None;
+    let mut export = ExportOptions {
+        format: export::Format::Ppm,
+        scale: DEFAULT_EXPORT_SCALE,
+        every: 1,
+    };
+    let mut steps = DEFAULT_EXPORT_STEPS;
+    let mut checked = false;
+    let mut samples = false;
+    let mut mode = Mode::Solve;
+    let mut format = Format::Text;
+    let mut level = Level::Normal;
+
+    while let Some(arg) = args.next() {
+        if let Some(new_level) = Level::from_flag(&arg) {
+            level = new_level;
+            continue;
+        }
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(value()?.parse()?),
+            "--part" | "-p" => part = Some(value()?.parse()?),
+            "--input" | "-i" => input = Some(value()?),
+            "--answers" => answers = Some(value()?),
+            "--verify" => mode = Mode::Verify,
+            "--bench" => mode = Mode::Bench,
+            "--differential" => mode = Mode::Differential,
+            "--fuzz" => mode = Mode::Fuzz,
+            "--all" => mode = Mode::All,
+            "--view" => mode = Mode::View,
+            "--export" => {
+                mode = Mode::Export;
+                export_dir = Some(value()?);
+            }
+            "--html" => {
+                mode = Mode::Html;
+                html_path = Some(value()?);
+            }
+            "--image-format" => {
+                let name = value()?;
+                export.format = export::Format::from_name(&name)
+                    .ok_or_else(|| format!("Unknown image format {}", name))?;
+            }
+            "--scale" => export.scale = value()?.parse()?,
+            "--every" => export.every = value()?.parse()?,
+            "--steps" => steps = value()?.parse()?,
+            "--timeout" => timeout_secs = value()?.parse()?,
+            "--params" => params = Some(value()?),
+            "--profile" => profile_name = Some(value()?),
+            "--all-profiles" => all_profiles = true,
+            "--checked" => checked = true,
+            "--watch" => mode = Mode::Watch,
+            "--samples" => samples = true,
+            "--param" => {
+                let (name, value) = params::parse_override(&value()?)?;
+                overrides.insert(name, value);
+            }
+            "--cases" => cases = Some(value()?.parse()?),
+            "--seed" => seed = value()?.parse()?,
+            "--runs" => runs = value()?.parse()?,
+            "--save" => save = Some(value()?),
+            "--baseline" => baseline = Some(value()?),
+            "--format" => {
+                format = match value()?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    other => return Err(format!("Unknown format {}", other).into()),
+                }
+            }
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ => return Err(format!("Unknown argument {}", arg).into()),
+        }
+    }
+
+    if matches!(mode, Mode::Solve | Mode::View | Mode::Export | Mode::Watch) && day.is_none() {
+        return Err("--day is required".into());
+    }
+    if mode == Mode::Watch && input.as_deref() == Some("-") {
+        return Err("--watch re-reads the input, so it can't come from stdin".into());
+    }
+    if samples && mode != Mode::Watch {
+        return Err("--samples only works with --watch".into());
+    }
+    if mode == Mode::View && input.as_deref() == Some("-") {
+        return Err("--view reads keys from stdin, so the input can't come from there".into());
+    }
+    if let Some(d) = day.filter(|&d| days::get(d).is_none()) {
+        return Err(format!("No solver for day {}", d).into());
+    }
+    if let Some(p) = part.filter(|&p| p != 1 && p != 2) {
+        return Err(format!("Part must be 1 or 2, not {}", p).into());
+    }
+    if runs == 0 {
+        return Err("--runs must be at least 1".into());
+    }
+    let cases = cases.unwrap_or(match mode {
+        Mode::Fuzz => DEFAULT_FUZZ_CASES,
+        _ => DEFAULT_DIFFERENTIAL_CASES,
+    });
+    if cases == 0 {
+        return Err("--cases must be at least 1".into());
+    }
+    if all_profiles && mode != Mode::Verify {
+        return Err("--all-profiles only works with --verify".into());
+    }
+    if all_profiles && (answers.is_some() || profile_name.is_some()) {
+        return Err("--all-profiles can't be combined with --answers or --profile".into());
+    }
+    let profile = match profile_name {
+        Some(name) => Profile::named(&name)?,
+        None => Profile::from_env().map_err(|e| format!("{} (from {})", e, PROFILE_VAR))?,
+    };
+    if !overrides.is_empty() && day.is_none() {
+        return Err("--param needs --day".into());
+    }
+    if export.scale == 0 || export.every == 0 {
+        return Err("--scale and --every must be at least 1".into());
+    }
+    if timeout_secs == 0 {
+        return Err("--timeout must be at least 1".into());
+    }
+    if mode == Mode::Bench && input.is_some() && day.is_none() {
+        return Err("--input needs --day when benchmarking".into());
+    }
+
+    Ok(Args {
+        mode,
+        format,
+        level,
+        day,
+        part,
+        input,
+        answers,
+        runs,
+        save,
+        baseline,
+        cases,
+        seed,
+        timeout: Duration::from_secs(timeout_secs),
+        params,
+        overrides,
+        profile,
+        all_profiles,
+        export_dir,
+        export,
+        html_path,
+        steps,
+        checked,
+        samples,
+    })
+}
+
+/// What to call the input in messages: its path, or "stdin".
+fn input_name(day: usize, args: &Args) -> String {
+    match args.input.as_deref() {
+        Some("-") => "stdin".to_string(),
+        Some(path) => path.to_string(),
+        None => args.profile.input_path(day),
+    }
+}
+
+fn read_input(day: usize, args: &Args) -> Result<String, InputError> {
+    match args.input.as_deref() {
+        Some("-") => input::read(io::stdin()).map_err(|e| e.in_file(input_name(day, args))),
+        Some(path) => input::read_file(path),
+        None => input::read_file(args.profile.input_path(day)),
+    }
+}
+
+fn load_params(args: &Args) -> Result<Vec<ParamsEntry>, Box<dyn error::Error>> {
+    params::load(args.params.as_deref().unwrap_or(params::PARAMS_PATH))
+}
+
+/// Day `day`'s solver with the parameters for the input it's about to be run
+/// on, from the params file and --param.
+fn configured(
+    day: usize,
+    input_name: &str,
+    entries: &[ParamsEntry],
+    args: &Args,
+) -> Result<Box<dyn AnySolution>, Box<dyn error::Error>> {
+    let params = params::lookup(entries, day, input_name, &args.overrides);
+    if !params.is_empty() {
+        verbose!("Day {} params: {}", day, params::describe(&params));
+    }
+    days::configured(day, &params)
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), Box<dyn error::Error>> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn run_verify(args: &Args) -> Result<(), Box<dyn error::Error>> {
+    let all_expected = match args.answers.as_deref() {
+        Some(path) => answers::load(path)?,
+        None if args.all_profiles => {
+            let mut all_expected = Vec::new();
+            for profile in Profile::all()? {
+                all_expected.extend(profile.load_answers()?);
+            }
+            all_expected
+        }
+        None => args.profile.load_answers()?,
+    };
+    let expected: Vec<answers::Expected> = all_expected
+        .into_iter()
+        .filter(|e| args.day.is_none_or(|d| e.day == d))
+        .map(|mut e| {
+            if args.part == Some(1) {
+                e.part2 = None;
+            } else if args.part == Some(2) {
+                e.part1 = None;
+            }
+            e
+        })
+        .collect();
+
+    let checks = verify::check_all(&expected, &load_params(args)?, &args.overrides);
+    match args.format {
+        Format::Text => verify::print_table(&checks),
+        Format::Json => print_json(&VerifyReport::new(&checks))?,
+    }
+
+    if checks.iter().any(|c| !c.outcome.is_pass()) {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_bench(args: &Args) -> Result<(), Box<dyn error::Error>> {
+    let baseline = args
+        .baseline
+        .as_deref()
+        .map(BenchReport::load)
+        .transpose()?;
+
+    let day_nums: Vec<usize> = match args.day {
+        Some(day) => vec![day],
+        None => (1..=days::ALL.len()).collect(),
+    };
+
+    let entries = load_params(args)?;
+    let mut report = BenchReport {
+        runs: args.runs,
+        days: Vec::new(),
+    };
+    for day_num in day_nums {
+        let input_name = input_name(day_num, args);
+        let result = configured(day_num, &input_name, &entries, args).and_then(|day| {
+            let input = read_input(day_num, args)?;
+            bench::bench_day(&*day, &input_name, &input, args.runs)
+        });
+        match result {
+            Ok(day_bench) => report.days.push(day_bench),
+            Err(e) => eprintln!("Day {} failed: {}", day_num, e),
+        }
+    }
+
+    match args.format {
+        Format::Text => bench::print_table(&report, baseline.as_ref()),
+        Format::Json => print_json(&report)?,
+    }
+
+    if let Some(path) = args.save.as_deref() {
+        report.save(path)?;
+    }
+    Ok(())
+}
+
+fn run_differential(args: &Args) -> Result<(), Box<dyn error::Error>> {
+    let reports: Vec<differential::RunReport> = days::DIFFERENTIAL
+        .iter()
+        .filter(|d| args.day.is_none_or(|day| d.day() == day))
+        .map(|&d| differential::run(d, args.seed, args.cases, DIFFERENTIAL_MAX_SIZE))
+        .collect();
+    if reports.is_empty() {
+        return Err(format!("No reference solver for day {}", args.day.unwrap()).into());
+    }
+
+    match args.format {
+        Format::Text => differential::print_reports(&reports),
+        Format::Json => print_json(&reports)?,
+    }
+
+    if reports.iter().any(|r| r.mismatch.is_some()) {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_fuzz(args: &Args) -> Result<(), Box<dyn error::Error>> {
+    let reports: Vec<fuzz::FuzzReport> = days::ALL
+        .iter()
+        .filter(|d| args.day.is_none_or(|day| d.day() == day))
+        .map(|&d| fuzz::run(d, args.seed, args.cases))
+        .collect();
+
+    match args.format {
+        Format::Text => fuzz::print_reports(&reports),
+        Format::Json => print_json(&reports)?,
+    }
+
+    if reports.iter().any(|r| r.crash.is_some()) {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_all(args: &Args) -> Result<(), Box<dyn error::Error>> {
+    let entries = load_params(args)?;
+    let solutions = (1..=days::ALL.len())
+        .map(|day| configured(day, &args.profile.input_path(day), &entries, args))
+        .collect::<Result<_, _>>()?;
+    let report = run_all::run(solutions, &args.profile, args.timeout);
+    match args.format {
+        Format::Text => run_all::print_table(&report),
+        Format::Json => print_json(&report)?,
+    }
+
+    if report.failed > 0 {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_solve(args: &Args) -> Result<(), Box<dyn error::Error>> {
+    let day_num = args.day.unwrap();
+    let input = read_input(day_num, args)?;
+    let input_name = input_name(day_num, args);
+    let day = configured(day_num, &input_name, &load_params(args)?, args)?;
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let report = report::solve(&*day, &input_name, &input, &parts);
+
+    if args.format == Format::Json {
+        print_json(&report)?;
+        if !report.is_ok() {
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let log_phase = |phase: &str, elapsed_ns: u64, diagnostics: &Diagnostics| {
+        verbose!(
+            "Day {} {} took {:?}",
+            day_num,
+            phase,
+            Duration::from_nanos(elapsed_ns)
+        );
+        for (name, value) in diagnostics {
+            info!("Day {} {} {}: {}", day_num, phase, name, value);
+        }
+    };
+
+    log_phase("parse", report.parse.elapsed_ns, &report.parse.diagnostics);
+    if let Some(e) = report.parse.error {
+        return Err(e.into());
+    }
+    for (part, phase) in parts.into_iter().zip(report.parts) {
+        log_phase(
+            &format!("part {}", part),
+            phase.elapsed_ns,
+            &phase.diagnostics,
+        );
+        if let Some(e) = phase.error {
+            return Err(e.into());
+        }
+        let answer = phase.answer.unwrap_or_default();
+        if answer.contains('\n') {
+            println!("Day {} part {}:\n{}", day_num, part, answer);
+        } else {
+            println!("Day {} part {}: {}", day_num, part, answer);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_view(args: &Args) -> Result<(), Box<dyn error::Error>> {
+    let day_num = args.day.unwrap();
+    let part = args.part.unwrap_or(1);
+    let input = read_input(day_num, args)?;
+    let input_name = input_name(day_num, args);
+    let day = configured(day_num, &input_name, &load_params(args)?, args)?;
+    let parsed = day.parse(&input).map_err(|e| e.in_file(&input_name))?;
+
+    let mut viewer = Viewer::new(day.simulation(&*parsed, part)?);
+    let title = format!("Day {} part {} ({})", day_num, part, input_name);
+    viewer::interact(&mut viewer, &title)?;
+    Ok(())
+}
+
+/// The files written for one part by --export.
+#[derive(Serialize)]
+struct Exported {
+    day: usize,
+    part: usize,
+    files: Vec<String>,
+}
+
+fn run_export(args: &Args) -> Result<(), Box<dyn error::Error>> {
+    let day_num = args.day.unwrap();
+    let input = read_input(day_num, args)?;
+    let input_name = input_name(day_num, args);
+    let day = configured(day_num, &input_name, &load_params(args)?, args)?;
+    let parsed = day.parse(&input).map_err(|e| e.in_file(&input_name))?;
+    let dir = Path::new(args.export_dir.as_deref().unwrap());
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let mut exported = Vec::new();
+    for part in parts {
+        let frames = day.frames(&*parsed, part, args.steps)?;
+        let stem = format!("day{}-part{}", day_num, part);
+        let files = export::write_frames(frames, dir, &stem, args.export)?;
+        if args.format == Format::Text {
+            match &files[..] {
+                [file] => println!("Day {} part {}: {}", day_num, part, file),
+                files => println!(
+                    "Day {} part {}: {} frames, {} to {}",
+                    day_num,
+                    part,
+                    files.len(),
+                    files.first().map_or("", |f| f),
+                    files.last().map_or("", |f| f)
+                ),
+            }
+        }
+        exported.push(Exported {
+            day: day_num,
+            part,
+            files,
+        });
+    }
+
+    if args.format == Format::Json {
+        print_json(&exported)?;
+    }
+    Ok(())
+}
+
+fn run_html(args: &Args) -> Result<(), Box<dyn error::Error>> {
+    let day_nums: Vec<usize> = match args.day {
+        Some(day) => vec![day],
+        None => (1..=days::ALL.len()).collect(),
+    };
+
+    let entries = load_params(args)?;
+    let mut reports = Vec::new();
+    for day_num in day_nums {
+        let input_name = input_name(day_num, args);
+        let result = configured(day_num, &input_name, &entries, args).and_then(|day| {
+            let input = read_input(day_num, args)?;
+            info!("Day {}", day_num);
+            Ok(html::day_report(&*day, &input_name, &input, args.steps))
+        });
+        match result {
+            Ok(report) => reports.push(report),
+            Err(e) => eprintln!("Day {} failed: {}", day_num, e),
+        }
+    }
+
+    let title = match args.profile.name() {
+        "default" => "Advent of Code 2022".to_string(),
+        name => format!("Advent of Code 2022 ({})", name),
+    };
+    let path = args.html_path.as_deref().unwrap();
+    fs::write(path, html::render(&title, &reports))
+        .map_err(|e| format!("Couldn't write {}: {}", path, e))?;
+    match args.format {
+        Format::Text => println!("{}", path),
+        Format::Json => print_json(&reports.iter().map(|r| &r.report).collect::<Vec<_>>())?,
+    }
+
+    if reports.iter().any(|r| !r.report.is_ok()) {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// The options to pass on to each run of the solver under --watch.
+#[cfg(target_os = "linux")]
+fn forwarded_args(args: &Args) -> Vec<String> {
+    let mut forwarded = Vec::new();
+    if let Some(part) = args.part {
+        forwarded.extend(["--part".to_string(), part.to_string()]);
+    }
+    if let Some(params) = &args.params {
+        forwarded.extend(["--params".to_string(), params.clone()]);
+    }
+    for (name, value) in &args.overrides {
+        forwarded.extend(["--param".to_string(), format!("{}={}", name, value)]);
+    }
+    if args.checked {
+        forwarded.push("--checked".to_string());
+    }
+    forwarded
+}
+
+#[cfg(target_os = "linux")]
+fn run_watch(args: &Args) -> Result<(), Box<dyn error::Error>> {
+    use std::path::PathBuf;
+
+    use aoc2022::{
+        solution,
+        watch::{self, Answers, Watcher},
+    };
+
+    let day = args.day.unwrap();
+    let mut inputs = vec![input_name(day, args)];
+    if args.samples {
+        inputs.push(solution::sample_path(day));
+    }
+    let files: Vec<PathBuf> = inputs.iter().map(PathBuf::from).collect();
+    let sources = [PathBuf::from("src"), PathBuf::from("src/days")];
+    let mut watcher = Watcher::new(&files, &sources)?;
+    let forwarded = forwarded_args(args);
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    // Only answers that were found are kept to compare against, so that
+    // fixing a mistake shows the last answer from before it
+    let mut previous = Answers::new();
+    for run in 1.. {
+        let mut current = Answers::new();
+        for input in &inputs {
+            match watch::solve_with_cargo(day, Path::new(input), &forwarded) {
+                Ok(report) => current.extend(watch::answers(&report, &parts)),
+                Err(e) => {
+                    for &part in &parts {
+                        current.insert((input.clone(), part), Err(e.clone()));
+                    }
+                }
+            }
+        }
+        println!("Day {}, run {}:", day, run);
+        print!("{}", watch::compare(&inputs, &current, &previous));
+        previous.extend(current.into_iter().filter(|(_, answer)| answer.is_ok()));
+
+        info!("Watching for changes...");
+        let changed = watcher.wait(None)?;
+        for path in changed {
+            verbose!("Changed: {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn run_watch(_args: &Args) -> Result<(), Box<dyn error::Error>> {
+    Err("--watch needs Linux, as it uses inotify".into())
+}
+
+fn run() -> Result<(), Box<dyn error::Error>> {
+    let args = parse_args(env::args().skip(1))?;
+    logging::set_level(args.level);
+    checked::set_enabled(args.checked);
+    match args.mode {
+        Mode::Solve => run_solve(&args),
+        Mode::Verify => run_verify(&args),
+        Mode::Bench => run_bench(&args),
+        Mode::Differential => run_differential(&args),
+        Mode::Fuzz => run_fuzz(&args),
+        Mode::All => run_all(&args),
+        Mode::View => run_view(&args),
+        Mode::Export => run_export(&args),
+        Mode::Html => run_html(&args),
+        Mode::Watch => run_watch(&args),
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
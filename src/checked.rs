@@ -0,0 +1,158 @@
+use std::{
+    error, fmt,
+    ops::{Add, Div, Mul, Sub},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turns checking on or off for the whole process. Off, the operations below
+/// behave just like the plain operators: a release build wraps around on
+/// overflow and a debug build panics.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether arithmetic is being checked. Cheap enough to call inside hot loops.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// An operation whose result doesn't fit in its type, or a division by zero,
+/// caught in checked mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub day: usize,
+    /// What was being worked out, with the values involved, e.g. "worry level
+    /// (93 * 89)".
+    pub operation: String,
+    pub division_by_zero: bool,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let problem = if self.division_by_zero {
+            "division by zero"
+        } else {
+            "overflow"
+        };
+        write!(f, "Day {}: {} in {}", self.day, problem, self.operation)
+    }
+}
+
+impl error::Error for Overflow {}
+
+/// The integer types the days do their sums in.
+pub trait Int:
+    Copy
+    + fmt::Display
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    const ZERO: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_div(self, other: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn pow(self, exp: u32) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(impl Int for $t {
+            const ZERO: Self = 0;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn checked_div(self, other: Self) -> Option<Self> {
+                <$t>::checked_div(self, other)
+            }
+
+            fn checked_pow(self, exp: u32) -> Option<Self> {
+                <$t>::checked_pow(self, exp)
+            }
+
+            fn pow(self, exp: u32) -> Self {
+                <$t>::pow(self, exp)
+            }
+        })*
+    };
+}
+
+impl_int!(usize, isize, i64);
+
+/// `raw` unless checking is on, in which case `checked`, failing with an
+/// `Overflow` that describes the operation as `what (a symbol b)`.
+fn apply<T: Int, U: fmt::Display>(
+    day: usize,
+    what: &str,
+    (a, symbol, b): (T, &str, U),
+    raw: impl FnOnce() -> T,
+    checked: impl FnOnce() -> Option<T>,
+) -> Result<T, Overflow> {
+    if !enabled() {
+        return Ok(raw());
+    }
+    checked().ok_or_else(|| Overflow {
+        day,
+        operation: format!("{} ({} {} {})", what, a, symbol, b),
+        division_by_zero: false,
+    })
+}
+
+/// `a + b` for day `day`, where `what` says what the sum is for.
+pub fn add<T: Int>(day: usize, what: &str, a: T, b: T) -> Result<T, Overflow> {
+    apply(day, what, (a, "+", b), || a + b, || a.checked_add(b))
+}
+
+pub fn sub<T: Int>(day: usize, what: &str, a: T, b: T) -> Result<T, Overflow> {
+    apply(day, what, (a, "-", b), || a - b, || a.checked_sub(b))
+}
+
+pub fn mul<T: Int>(day: usize, what: &str, a: T, b: T) -> Result<T, Overflow> {
+    apply(day, what, (a, "*", b), || a * b, || a.checked_mul(b))
+}
+
+/// `a / b`, which in checked mode also catches division by zero.
+pub fn div<T: Int>(day: usize, what: &str, a: T, b: T) -> Result<T, Overflow> {
+    apply(day, what, (a, "/", b), || a / b, || a.checked_div(b)).map_err(|e| Overflow {
+        division_by_zero: b == T::ZERO,
+        ..e
+    })
+}
+
+pub fn pow<T: Int>(day: usize, what: &str, base: T, exp: u32) -> Result<T, Overflow> {
+    apply(
+        day,
+        what,
+        (base, "^", exp),
+        || base.pow(exp),
+        || base.checked_pow(exp),
+    )
+}
+
+/// The sum of `values`, checking each addition.
+pub fn sum<T: Int>(
+    day: usize,
+    what: &str,
+    values: impl IntoIterator<Item = T>,
+) -> Result<T, Overflow> {
+    values
+        .into_iter()
+        .try_fold(T::ZERO, |total, value| add(day, what, total, value))
+}
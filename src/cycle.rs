@@ -0,0 +1,301 @@
+use std::{collections::HashMap, convert::TryFrom, error, fmt, hash::Hash};
+
+use serde::Serialize;
+
+/// A value that can be worked out for later steps of a repeating simulation:
+/// the growth over one period is found once, then scaled up by the number of
+/// periods left. Both return `None` if the result wouldn't fit.
+pub trait Extrapolate: Sized {
+    /// How much the value has grown since `earlier`.
+    fn growth_since(&self, earlier: &Self) -> Option<Self>;
+    /// The value after growing by `growth` another `times` times.
+    fn grow(&self, growth: &Self, times: usize) -> Option<Self>;
+}
+
+macro_rules! impl_extrapolate {
+    ($($t:ty),*) => {
+        $(impl Extrapolate for $t {
+            fn growth_since(&self, earlier: &Self) -> Option<Self> {
+                self.checked_sub(*earlier)
+            }
+
+            fn grow(&self, growth: &Self, times: usize) -> Option<Self> {
+                <$t>::try_from(times)
+                    .ok()
+                    .and_then(|times| growth.checked_mul(times))
+                    .and_then(|total| self.checked_add(total))
+            }
+        })*
+    };
+}
+
+impl_extrapolate!(i32, i64, isize, u32, u64, usize);
+
+/// Several values extrapolated side by side, such as a count for each of a
+/// number of things.
+impl<T: Extrapolate> Extrapolate for Vec<T> {
+    fn growth_since(&self, earlier: &Self) -> Option<Self> {
+        if self.len() != earlier.len() {
+            return None;
+        }
+        self.iter()
+            .zip(earlier)
+            .map(|(value, earlier)| value.growth_since(earlier))
+            .collect()
+    }
+
+    fn grow(&self, growth: &Self, times: usize) -> Option<Self> {
+        if self.len() != growth.len() {
+            return None;
+        }
+        self.iter()
+            .zip(growth)
+            .map(|(value, growth)| value.grow(growth, times))
+            .collect()
+    }
+}
+
+/// A repeating stretch of a simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Cycle<V> {
+    /// The first step whose state recurs.
+    pub start: usize,
+    /// How many steps it takes for the state to come around again.
+    pub period: usize,
+    /// How much the value grows over one period.
+    pub delta: V,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extrapolation<V> {
+    /// The value after the requested number of steps.
+    pub value: V,
+    /// The cycle used to skip ahead, or `None` if the simulation was run all
+    /// the way through without one turning up.
+    pub cycle: Option<Cycle<V>>,
+}
+
+/// A cycle was found, but the value it leads to doesn't fit in its type, or
+/// the value went down over the cycle when it can't go negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRange {
+    pub steps: usize,
+    pub start: usize,
+    pub period: usize,
+}
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The value after {} steps, repeating every {} steps from step {}, is out of range",
+            self.steps, self.period, self.start
+        )
+    }
+}
+
+impl error::Error for OutOfRange {}
+
+/// Runs `step` on `state` until `steps` steps have been taken, returning
+/// `value` of the final state. If two states share a `key`, everything from
+/// then on is assumed to repeat, with `value` growing by the same amount each
+/// time around, and the rest of the run is worked out rather than simulated.
+///
+/// `key` can return `None` for states that aren't yet worth comparing, such
+/// as before a simulation has settled down.
+pub fn extrapolate<S, K, V>(
+    mut state: S,
+    steps: usize,
+    mut key: impl FnMut(&S) -> Option<K>,
+    mut value: impl FnMut(&S) -> V,
+    mut step: impl FnMut(&mut S),
+) -> Result<Extrapolation<V>, OutOfRange>
+where
+    K: Eq + Hash,
+    V: Extrapolate + Clone,
+{
+    // The step at which each key was first seen
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    // The value after each step so far, starting from the initial state
+    let mut values: Vec<V> = Vec::new();
+
+    for current in 0..steps {
+        values.push(value(&state));
+
+        if let Some(k) = key(&state) {
+            if let Some(&start) = seen.get(&k) {
+                let period = current - start;
+                let cycles = (steps - start) / period;
+                let remainder = (steps - start) % period;
+                let out_of_range = || OutOfRange {
+                    steps,
+                    start,
+                    period,
+                };
+                let delta = values[current]
+                    .growth_since(&values[start])
+                    .ok_or_else(out_of_range)?;
+                let value = values[start + remainder]
+                    .grow(&delta, cycles)
+                    .ok_or_else(out_of_range)?;
+
+                return Ok(Extrapolation {
+                    value,
+                    cycle: Some(Cycle {
+                        start,
+                        period,
+                        delta,
+                    }),
+                });
+            }
+            seen.insert(k, current);
+        }
+
+        step(&mut state);
+    }
+
+    Ok(Extrapolation {
+        value: value(&state),
+        cycle: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A counter that goes up by each of `lead_in` in turn, then by each of
+    /// `cycle` over and over.
+    struct Counter {
+        lead_in: Vec<u64>,
+        cycle: Vec<u64>,
+    }
+
+    impl Counter {
+        /// Where the counter is in its pattern at `step`.
+        fn position(&self, step: usize) -> usize {
+            match step.checked_sub(self.lead_in.len()) {
+                None => step,
+                Some(into_cycle) => self.lead_in.len() + into_cycle % self.cycle.len(),
+            }
+        }
+
+        fn increase(&self, step: usize) -> u64 {
+            let position = self.position(step);
+            match self.lead_in.get(position) {
+                Some(&increase) => increase,
+                None => self.cycle[position - self.lead_in.len()],
+            }
+        }
+
+        fn extrapolate(&self, steps: usize) -> Extrapolation<u64> {
+            extrapolate(
+                (0, 0),
+                steps,
+                |&(step, _)| Some(self.position(step)),
+                |&(_, total)| total,
+                |(step, total)| {
+                    *total += self.increase(*step);
+                    *step += 1;
+                },
+            )
+            .unwrap()
+        }
+
+        fn simulate(&self, steps: usize) -> u64 {
+            (0..steps).map(|step| self.increase(step)).sum()
+        }
+    }
+
+    #[test]
+    fn cycle_from_the_first_step() {
+        let counter = Counter {
+            lead_in: vec![],
+            cycle: vec![3, 1, 4],
+        };
+        let extrapolation = counter.extrapolate(3_000_000);
+        assert_eq!(extrapolation.value, 8_000_000);
+        assert_eq!(
+            extrapolation.cycle,
+            Some(Cycle {
+                start: 0,
+                period: 3,
+                delta: 8
+            })
+        );
+    }
+
+    #[test]
+    fn cycle_after_a_lead_in() {
+        let counter = Counter {
+            lead_in: vec![7, 2],
+            cycle: vec![3, 1, 4],
+        };
+        let extrapolation = counter.extrapolate(2 + 3_000_000);
+        assert_eq!(extrapolation.value, 9 + 8_000_000);
+        assert_eq!(
+            extrapolation.cycle,
+            Some(Cycle {
+                start: 2,
+                period: 3,
+                delta: 8
+            })
+        );
+    }
+
+    #[test]
+    fn target_inside_the_lead_in() {
+        let counter = Counter {
+            lead_in: vec![7, 2, 5, 5],
+            cycle: vec![3, 1, 4],
+        };
+        for steps in 0..=4 {
+            let extrapolation = counter.extrapolate(steps);
+            assert_eq!(extrapolation.value, counter.simulate(steps));
+            assert_eq!(extrapolation.cycle, None);
+        }
+    }
+
+    #[test]
+    fn target_partway_through_a_cycle() {
+        let counter = Counter {
+            lead_in: vec![7, 2],
+            cycle: vec![3, 1, 4, 1, 5],
+        };
+        for steps in 2 + 5 * 100..2 + 5 * 101 {
+            assert_eq!(counter.extrapolate(steps).value, counter.simulate(steps));
+        }
+    }
+
+    #[test]
+    fn several_values_at_once() {
+        let extrapolation = extrapolate(
+            0,
+            1001,
+            |&step| Some(step % 2),
+            |&step| vec![step, step * 2 + step % 2],
+            |step| *step += 1,
+        )
+        .unwrap();
+        assert_eq!(extrapolation.value, vec![1001, 2003]);
+    }
+
+    #[test]
+    fn values_out_of_range() {
+        let result = extrapolate(
+            0usize,
+            usize::MAX,
+            |&step| Some(step % 2),
+            |&step| step as u32 * 1000,
+            |step| *step += 1,
+        );
+        assert_eq!(
+            result,
+            Err(OutOfRange {
+                steps: usize::MAX,
+                start: 0,
+                period: 2
+            })
+        );
+    }
+}
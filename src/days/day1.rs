@@ -0,0 +1,46 @@
+use std::error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::InputError,
+    input::{parse_blocks, parse_lines},
+    solution::Solution,
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: usize = 1;
+
+    /// Total calories carried by each elf, in input order.
+    type Parsed = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        // Each elf's snacks are a block of lines
+        parse_blocks(input, |block| {
+            let calories: Vec<usize> = parse_lines(&block.text(), |line| Ok(line.parse()?))?;
+            calories
+                .iter()
+                .try_fold(0usize, |total, &c| total.checked_add(c))
+                .ok_or_else(|| InputError::malformed("calorie total overflows"))
+        })
+    }
+
+    fn part1(&self, sums: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(*sums.iter().max().ok_or("No elves")?)
+    }
+
+    fn part2(&self, sums: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let mut sums = sums.clone();
+        sums.sort();
+        let top_sums = &sums[sums.len().saturating_sub(3)..];
+        Ok(top_sums
+            .iter()
+            .try_fold(0usize, |total, &sum| total.checked_add(sum))
+            .ok_or("Calorie total of the top three elves overflows")?)
+    }
+}
@@ -0,0 +1,88 @@
+use std::error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::InputError, input::parse_lines, solution::Solution};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+/// Runs the program, returning the sum of the signal strengths and the image
+/// drawn on the CRT.
+fn run(program: &[Instruction]) -> (i32, String) {
+    let mut cycle = 0;
+    let mut x_reg = 1;
+    let mut score = 0;
+    let mut screen = String::new();
+
+    for instruction in program {
+        let instruction_cycles = match instruction {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        };
+
+        for _ in 0..instruction_cycles {
+            let h_pos: i32 = cycle % 40;
+
+            if (h_pos - x_reg).abs() <= 1 {
+                screen.push('#');
+            } else {
+                screen.push('.');
+            }
+
+            if cycle > 0 && h_pos == 39 {
+                screen.push('\n');
+            }
+
+            cycle += 1;
+
+            if cycle >= 20 && (cycle - 20) % 40 == 0 {
+                score += cycle * x_reg;
+            }
+        }
+
+        if let Instruction::Addx(arg) = instruction {
+            x_reg += arg;
+        }
+    }
+
+    (score, screen.trim_end().to_owned())
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: usize = 10;
+
+    type Parsed = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        parse_lines(input, |line| {
+            let mut items = line.split_ascii_whitespace();
+            match items.next().unwrap() {
+                "noop" => Ok(Instruction::Noop),
+                "addx" => Ok(Instruction::Addx(
+                    items
+                        .next()
+                        .ok_or_else(|| InputError::malformed("no argument"))?
+                        .parse()?,
+                )),
+                other => Err(InputError::unknown_token(other)),
+            }
+        })
+    }
+
+    fn part1(&self, program: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(run(program).0)
+    }
+
+    fn part2(&self, program: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        Ok(run(program).1)
+    }
+}
@@ -0,0 +1,199 @@
+use std::{collections::HashMap, error};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    checked::{self, Overflow},
+    cycle::extrapolate,
+    diagnostics,
+    error::InputError,
+    input::{self, Block},
+    solution::Solution,
+};
+
+#[derive(Debug, Clone)]
+enum Op {
+    AddConstant(usize),
+    MulConstant(usize),
+    Square,
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    items: Vec<usize>,
+    op: Op,
+    test_divisor: usize,
+    true_tgt_idx: usize,
+    false_tgt_idx: usize,
+    inspections: usize,
+}
+
+fn run_round(monkeys: &mut [Monkey], relief: bool, combined_primes: usize) -> Result<(), Overflow> {
+    for i in 0..monkeys.len() {
+        let mut move_targets: HashMap<usize, Vec<usize>> = HashMap::new();
+        {
+            let monkey = &mut monkeys[i];
+            monkey.inspections += monkey.items.len();
+            for item in monkey.items.drain(0..) {
+                let new_value = match monkey.op {
+                    Op::AddConstant(c) => checked::add(Day11::DAY, "worry level", item, c)?,
+                    Op::MulConstant(c) => checked::mul(Day11::DAY, "worry level", item, c)?,
+                    Op::Square => checked::mul(Day11::DAY, "worry level", item, item)?,
+                };
+                let new_value = if relief {
+                    new_value / 3
+                } else {
+                    new_value % combined_primes
+                };
+                let target_idx = if new_value % monkey.test_divisor == 0 {
+                    monkey.true_tgt_idx
+                } else {
+                    monkey.false_tgt_idx
+                };
+                move_targets.entry(target_idx).or_default().push(new_value);
+            }
+        }
+
+        for (&tgt_idx, moved_items) in move_targets.iter_mut() {
+            monkeys[tgt_idx].items.append(moved_items);
+        }
+    }
+    Ok(())
+}
+
+fn monkey_business(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief: bool,
+) -> Result<usize, Box<dyn error::Error>> {
+    let combined_primes = monkeys
+        .iter()
+        .try_fold(1usize, |product, m| product.checked_mul(m.test_divisor))
+        .ok_or_else(|| Overflow {
+            day: Day11::DAY,
+            operation: "the product of the monkeys' divisors".to_string(),
+            division_by_zero: false,
+        })?;
+    // The rounds can't return errors through `extrapolate`, so the first one
+    // stops the rest of the rounds from running and is returned afterwards
+    let mut overflow: Option<Overflow> = None;
+
+    // Once the items are back where they were in an earlier round, each
+    // monkey's inspections go up by the same amount every time around
+    let extrapolation = extrapolate(
+        monkeys.to_vec(),
+        rounds,
+        |monkeys| Some(monkeys.iter().map(|m| m.items.clone()).collect::<Vec<_>>()),
+        |monkeys| monkeys.iter().map(|m| m.inspections).collect::<Vec<_>>(),
+        |monkeys| {
+            if overflow.is_none() {
+                overflow = run_round(monkeys, relief, combined_primes).err();
+            }
+        },
+    )?;
+    if let Some(e) = overflow {
+        return Err(e.into());
+    }
+    diagnostics::note("cycle", &extrapolation.cycle);
+
+    let mut inspections = extrapolation.value;
+
+    inspections.sort();
+    match inspections[..] {
+        [.., a, b] => Ok(checked::mul(Day11::DAY, "monkey business", a, b)?),
+        _ => Ok(inspections.iter().product()),
+    }
+}
+
+/// The lines in each monkey's description.
+const MONKEY_LINES: usize = 6;
+
+/// Parses the line `offset` lines into a monkey's description, which should
+/// start with the label `prefix`, by passing the rest of it to `parse`.
+fn field<'a, T>(
+    monkey: &Block<'a>,
+    offset: usize,
+    prefix: &str,
+    parse: impl FnOnce(&'a str) -> Result<T, InputError>,
+) -> Result<T, InputError> {
+    let line = monkey.lines[offset].trim();
+    line.strip_prefix(prefix)
+        .ok_or_else(|| InputError::malformed(format!("expected {:?}", prefix)))
+        .and_then(parse)
+        .map_err(|e| e.at(offset + 1, line))
+}
+
+fn parse_op(op: &str) -> Result<Op, InputError> {
+    match op.split_once(' ') {
+        Some(("+", operand)) => Ok(Op::AddConstant(operand.parse()?)),
+        Some(("*", "old")) => Ok(Op::Square),
+        Some(("*", operand)) => Ok(Op::MulConstant(operand.parse()?)),
+        _ => Err(InputError::unknown_token(op)),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: usize = 11;
+
+    type Parsed = Vec<Monkey>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let blocks = input::blocks(input);
+        let monkeys: Vec<Monkey> = blocks
+            .iter()
+            .map(|block| {
+                block.parse(|monkey| {
+                    if monkey.lines.len() != MONKEY_LINES {
+                        return Err(InputError::malformed(format!(
+                            "expected {} lines describing a monkey",
+                            MONKEY_LINES
+                        )));
+                    }
+                    let number = |offset, prefix| field(monkey, offset, prefix, |s| Ok(s.parse()?));
+
+                    Ok(Monkey {
+                        items: field(monkey, 1, "Starting items: ", |s| {
+                            Ok(s.split(", ").map(|s| s.parse()).collect::<Result<_, _>>()?)
+                        })?,
+                        op: field(monkey, 2, "Operation: new = old ", parse_op)?,
+                        test_divisor: field(monkey, 3, "Test: divisible by ", |s| {
+                            match s.parse()? {
+                                0 => Err(InputError::malformed("can't test for divisibility by 0")),
+                                divisor => Ok(divisor),
+                            }
+                        })?,
+                        true_tgt_idx: number(4, "If true: throw to monkey ")?,
+                        false_tgt_idx: number(5, "If false: throw to monkey ")?,
+                        inspections: 0,
+                    })
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        for (monkey, block) in monkeys.iter().zip(&blocks) {
+            for &(offset, tgt_idx) in &[(4, monkey.true_tgt_idx), (5, monkey.false_tgt_idx)] {
+                if tgt_idx >= monkeys.len() {
+                    return Err(
+                        InputError::invalid_reference(format!("no monkey {}", tgt_idx))
+                            .at(block.first_line + offset, block.lines[offset].trim()),
+                    );
+                }
+            }
+        }
+
+        Ok(monkeys)
+    }
+
+    fn part1(&self, monkeys: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        monkey_business(monkeys, 20, true)
+    }
+
+    fn part2(&self, monkeys: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        monkey_business(monkeys, 10000, false)
+    }
+}
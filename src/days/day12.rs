@@ -0,0 +1,153 @@
+use std::error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::InputError,
+    export::{blend, Image, Rgb},
+    grid::Grid,
+    pathfinding::{bfs, bfs_distances},
+    solution::Solution,
+};
+
+type Point = (usize, usize);
+
+pub struct Heightmap {
+    heights: Grid<usize>,
+    start: Point,
+    end: Point,
+}
+
+impl Heightmap {
+    /// The fewest steps from any of `starts` to the end point, climbing at
+    /// most one unit of height per step.
+    fn shortest_climb(&self, starts: impl IntoIterator<Item = Point>) -> Option<usize> {
+        let heights = &self.heights;
+        let path = bfs(
+            starts,
+            |&(x, y)| {
+                let max_height = heights[(x, y)] + 1;
+                heights
+                    .neighbours4(x, y)
+                    .filter(move |&tgt| heights[tgt] <= max_height)
+            },
+            |&pos| pos == self.end,
+        )?;
+        Some(path.cost)
+    }
+
+    /// The fewest steps from any of `starts` to every point, or `None` for
+    /// those that can't be reached.
+    fn distances(&self, starts: impl IntoIterator<Item = Point>) -> Grid<Option<usize>> {
+        let heights = &self.heights;
+        let reached = bfs_distances(starts, |&(x, y)| {
+            let max_height = heights[(x, y)] + 1;
+            heights
+                .neighbours4(x, y)
+                .filter(move |&tgt| heights[tgt] <= max_height)
+        });
+
+        let mut distances = heights.map(|_| None);
+        for (pos, distance) in reached {
+            distances[pos] = Some(distance);
+        }
+        distances
+    }
+
+    /// How far every point is from the nearest of `starts`, from dark blue
+    /// to yellow, with one of the shortest routes to the end in red. Points
+    /// that can't be reached are grey, lighter the higher they are.
+    fn distance_map(&self, starts: impl IntoIterator<Item = Point>) -> Image {
+        let heights = &self.heights;
+        let distances = self.distances(starts);
+        let furthest = distances.iter().filter_map(|(_, &d)| d).max().unwrap_or(0);
+
+        let mut image = Image::new(heights.width(), heights.height(), Rgb::WHITE);
+        for ((x, y), distance) in distances.iter() {
+            let color = match *distance {
+                Some(d) => blend(Rgb(20, 30, 120), Rgb(250, 220, 80), d, furthest),
+                None => blend(Rgb(60, 60, 60), Rgb(220, 220, 220), heights[(x, y)], 25),
+            };
+            image.set(x, y, color);
+        }
+
+        // Walk back from the end, always to a point one step nearer a start
+        let mut pos = self.end;
+        while let Some(d) = distances[pos].filter(|&d| d > 0) {
+            image.set(pos.0, pos.1, Rgb(208, 32, 32));
+            pos = heights
+                .neighbours4(pos.0, pos.1)
+                .find(|&prev| distances[prev] == Some(d - 1) && heights[pos] <= heights[prev] + 1)
+                .unwrap();
+        }
+        image.set(pos.0, pos.1, Rgb(208, 32, 32));
+        image
+    }
+
+    fn lowest_points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.heights
+            .iter()
+            .filter(|&(_, &height)| height == 0)
+            .map(|(pos, _)| pos)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: usize = 12;
+
+    type Parsed = Heightmap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let chars = Grid::parse(input, |c| match c {
+            'S' | 'E' | 'a'..='z' => Ok(c),
+            _ => Err(InputError::unknown_token(c)),
+        })?;
+
+        let find = |target: char| {
+            chars
+                .iter()
+                .find(|&(_, &c)| c == target)
+                .map(|(pos, _)| pos)
+        };
+        let start = find('S').ok_or_else(|| InputError::malformed("no start point"))?;
+        let end = find('E').ok_or_else(|| InputError::malformed("no end point"))?;
+
+        let heights = chars.map(|&c| match c {
+            'S' => 0,
+            'E' => 25,
+            _ => c as usize - 'a' as usize,
+        });
+
+        Ok(Heightmap {
+            heights,
+            start,
+            end,
+        })
+    }
+
+    fn part1(&self, map: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(map
+            .shortest_climb(Some(map.start))
+            .ok_or("No path from the start point")?)
+    }
+
+    fn part2(&self, map: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        Ok(map
+            .shortest_climb(map.lowest_points())
+            .ok_or("No path from any lowest point")?)
+    }
+
+    fn pictures(&self, map: &Self::Parsed, part: usize) -> Option<Vec<Image>> {
+        let image = if part == 1 {
+            map.distance_map(Some(map.start))
+        } else {
+            map.distance_map(map.lowest_points())
+        };
+        Some(vec![image])
+    }
+}
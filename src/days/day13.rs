@@ -0,0 +1,107 @@
+use std::{cmp::Ordering, error};
+
+use itertools::{EitherOrBoth, Itertools};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::InputError, input::parse_blocks, solution::Solution};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Packet {
+    Scalar(usize),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Scalar(a), Packet::Scalar(b)) => a.cmp(b),
+            (Packet::List(a_items), Packet::List(b_items)) => {
+                for either_or_both in a_items.iter().zip_longest(b_items.iter()) {
+                    match either_or_both {
+                        EitherOrBoth::Both(a, b) => {
+                            let a_vs_b = a.cmp(b);
+                            if a_vs_b != Ordering::Equal {
+                                return a_vs_b;
+                            }
+                        }
+                        EitherOrBoth::Right(_) => {
+                            return Ordering::Less;
+                        }
+                        EitherOrBoth::Left(_) => {
+                            return Ordering::Greater;
+                        }
+                    }
+                }
+                Ordering::Equal
+            }
+            (Packet::Scalar(a), b_list) => Packet::List(vec![Packet::Scalar(*a)]).cmp(b_list),
+            (a_list, Packet::Scalar(b)) => a_list.cmp(&Packet::List(vec![Packet::Scalar(*b)])),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn parse_packet(line: &str) -> Result<Packet, InputError> {
+    serde_json::from_str(line).map_err(|e| InputError::malformed(format!("bad packet ({})", e)))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: usize = 13;
+
+    type Parsed = Vec<(Packet, Packet)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        parse_blocks(input, |block| match block.lines[..] {
+            [a, b] => {
+                let packet = |number, line| parse_packet(line).map_err(|e| e.at(number, line));
+                Ok((packet(1, a)?, packet(2, b)?))
+            }
+            _ => Err(InputError::malformed("expected a pair of packets")),
+        })
+    }
+
+    fn part1(&self, pairs: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let ordered_indices: usize = pairs
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (packet_a, packet_b))| {
+                if packet_a > packet_b {
+                    None
+                } else {
+                    Some(idx + 1)
+                }
+            })
+            .sum();
+
+        Ok(ordered_indices)
+    }
+
+    fn part2(&self, pairs: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let mut packets: Vec<Packet> = pairs
+            .iter()
+            .flat_map(|(a, b)| vec![a.clone(), b.clone()])
+            .collect();
+
+        let two = Packet::List(vec![Packet::Scalar(2)]);
+        let six = Packet::List(vec![Packet::Scalar(6)]);
+        packets.push(two.clone());
+        packets.push(six.clone());
+        packets.sort();
+
+        let (two_pos, _) = packets.iter().find_position(|&p| p == &two).unwrap();
+        let (six_pos, _) = packets.iter().find_position(|&p| p == &six).unwrap();
+        let decoder_key = (two_pos + 1) * (six_pos + 1);
+        Ok(decoder_key)
+    }
+}
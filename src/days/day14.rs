@@ -0,0 +1,194 @@
+use std::error;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::InputError, grid::SparseGrid, input::parse_lines, solution::Solution, trace,
+    viewer::Simulation,
+};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum CellKind {
+    Rock,
+    Sand,
+}
+
+use CellKind::*;
+
+type Grid = SparseGrid<CellKind>;
+
+const SAND_SOURCE: (isize, isize) = (500, 0);
+
+/// Rock coordinates must be between 0 and this. Real inputs stay well inside
+/// it, and it keeps a typo from drawing a rock path billions of cells long.
+const MAX_COORDINATE: isize = 1000;
+
+/// Drops one unit of sand from the source. Returns false if it couldn't come to
+/// rest, either because it fell into the abyss below the lowest rock or
+/// because the source is already blocked.
+fn insert_sand(grid: &mut Grid, max_y: isize, has_floor: bool) -> bool {
+    let (mut x, mut y) = SAND_SOURCE;
+
+    loop {
+        if grid.get(x, y) == Some(&Sand) {
+            return false;
+        }
+
+        if y == max_y + 1 {
+            if has_floor {
+                grid.insert(x, y, Sand);
+                return true;
+            } else {
+                return false;
+            }
+        }
+
+        let mut found = false;
+        for &(next_x, next_y) in &[(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)] {
+            if !grid.contains(next_x, next_y) {
+                x = next_x;
+                y = next_y;
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            grid.insert(x, y, Sand);
+            return true;
+        }
+    }
+}
+
+/// Sand being poured onto the rocks, one unit per step.
+#[derive(Clone)]
+struct Pouring {
+    grid: Grid,
+    max_y: isize,
+    has_floor: bool,
+    sand_inserted: usize,
+}
+
+impl Pouring {
+    fn new(rocks: &Grid, has_floor: bool) -> Self {
+        Pouring {
+            grid: rocks.clone(),
+            max_y: rocks.bounds().map_or(0, |bounds| bounds.max.y),
+            has_floor,
+            sand_inserted: 0,
+        }
+    }
+}
+
+impl Simulation for Pouring {
+    fn step(&mut self) -> bool {
+        let inserted = insert_sand(&mut self.grid, self.max_y, self.has_floor);
+        if inserted {
+            self.sand_inserted += 1;
+        }
+        inserted
+    }
+
+    fn status(&self) -> String {
+        format!("{} units of sand at rest", self.sand_inserted)
+    }
+
+    fn rows(&self) -> Vec<String> {
+        self.grid
+            .render(|cell| match cell {
+                Some(&Rock) => '#',
+                Some(&Sand) => 'o',
+                None => '.',
+            })
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    fn origin(&self) -> (isize, isize) {
+        self.grid
+            .bounds()
+            .map_or((0, 0), |bounds| (bounds.min.x, bounds.min.y))
+    }
+}
+
+fn pour_sand(rocks: &Grid, has_floor: bool) -> usize {
+    let mut pouring = Pouring::new(rocks, has_floor);
+    while pouring.step() {
+        if pouring.sand_inserted.is_multiple_of(100) {
+            trace!("Day 14: {} units of sand at rest", pouring.sand_inserted);
+        }
+    }
+
+    pouring.sand_inserted
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: usize = 14;
+
+    /// The rock formations; no sand has been poured yet.
+    type Parsed = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let paths: Vec<Vec<(isize, isize)>> = parse_lines(input, |line| {
+            let points: Vec<(isize, isize)> = line
+                .split(" -> ")
+                .map(|s| {
+                    let (x, y) = s
+                        .split_once(',')
+                        .ok_or_else(|| InputError::malformed(format!("bad point {}", s)))?;
+                    let point: (isize, isize) = (x.parse()?, y.parse()?);
+                    if !(0..=MAX_COORDINATE).contains(&point.0)
+                        || !(0..=MAX_COORDINATE).contains(&point.1)
+                    {
+                        return Err(InputError::malformed(format!(
+                            "point {} is outside 0..={}",
+                            s, MAX_COORDINATE
+                        )));
+                    }
+                    Ok(point)
+                })
+                .collect::<Result<_, InputError>>()?;
+
+            if points
+                .iter()
+                .tuple_windows()
+                .any(|(a, b)| a.0 != b.0 && a.1 != b.1)
+            {
+                return Err(InputError::malformed("diagonal rock path"));
+            }
+            Ok(points)
+        })?;
+
+        let mut grid: Grid = SparseGrid::new();
+        for points in paths {
+            for (&(ax, ay), &(bx, by)) in points.iter().tuple_windows() {
+                for x in ax.min(bx)..=ax.max(bx) {
+                    for y in ay.min(by)..=ay.max(by) {
+                        grid.insert(x, y, Rock);
+                    }
+                }
+            }
+        }
+
+        Ok(grid)
+    }
+
+    fn part1(&self, rocks: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(pour_sand(rocks, false))
+    }
+
+    fn part2(&self, rocks: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        Ok(pour_sand(rocks, true))
+    }
+
+    fn simulation(&self, rocks: &Self::Parsed, part: usize) -> Option<Box<dyn Simulation>> {
+        Some(Box::new(Pouring::new(rocks, part == 2)))
+    }
+}
@@ -0,0 +1,322 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    error,
+    ops::RangeInclusive,
+};
+
+use crate::{
+    diagnostics,
+    differential::{Differential, Rng},
+    error::InputError,
+    geometry::Point2,
+    input::parse_lines,
+    line_pattern,
+    solution::Solution,
+};
+
+type Point = Point2<isize>;
+
+line_pattern! {
+    struct ReadingLine =
+        r"Sensor at x=([-\d]+), y=([-\d]+): closest beacon is at x=([-\d]+), y=([-\d]+)" {
+        sensor_x: isize,
+        sensor_y: isize,
+        beacon_x: isize,
+        beacon_y: isize,
+    }
+}
+
+fn merge_range(
+    row: &mut Vec<RangeInclusive<isize>>,
+    range: &RangeInclusive<isize>,
+    max_search: isize,
+) -> bool {
+    let (intersecting, separate): (Vec<_>, Vec<_>) = row.drain(..).partition(|r| {
+        RangeInclusive::contains(r, range.start())
+            || RangeInclusive::contains(range, r.start())
+            || *r.start() == range.end() + 1
+            || *range.start() == r.end() + 1
+    });
+    *row = separate;
+
+    let min = intersecting
+        .iter()
+        .map(|r| r.start())
+        .min()
+        .unwrap_or(range.start())
+        .min(range.start());
+    let max = intersecting
+        .iter()
+        .map(|r| r.end())
+        .max()
+        .unwrap_or(range.end())
+        .max(range.end());
+
+    if *min <= 0 && *max >= max_search {
+        return false;
+    }
+
+    row.push(*min..=*max);
+    true
+}
+
+/// A sensor and the closest beacon to it.
+type Reading = (Point, Point);
+
+/// The puzzle asks about a different row and search area for the example
+/// than for the real input, so those are carried on the solver.
+#[derive(Serialize, Deserialize)]
+pub struct Day15 {
+    pub target_row: isize,
+    pub max_search: isize,
+}
+
+impl Day15 {
+    pub const REAL: Day15 = Day15 {
+        target_row: 2000000,
+        max_search: 4000000,
+    };
+
+    pub const SAMPLE: Day15 = Day15 {
+        target_row: 10,
+        max_search: 20,
+    };
+}
+
+impl Solution for Day15 {
+    const DAY: usize = 15;
+
+    type Parsed = Vec<Reading>;
+    type Part1 = usize;
+    type Part2 = isize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        parse_lines(input, |line| {
+            let reading = ReadingLine::parse_line(line)?;
+            Ok((
+                Point::new(reading.sensor_x, reading.sensor_y),
+                Point::new(reading.beacon_x, reading.beacon_y),
+            ))
+        })
+    }
+
+    fn part1(&self, readings: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let y = self.target_row;
+
+        let mut covered: Vec<RangeInclusive<isize>> = readings
+            .iter()
+            .filter_map(|(sensor, beacon)| {
+                let half_width = sensor.manhattan(*beacon) - (sensor.y - y).abs();
+                if half_width < 0 {
+                    None
+                } else {
+                    Some((sensor.x - half_width)..=(sensor.x + half_width))
+                }
+            })
+            .collect();
+        covered.sort_by_key(|r| *r.start());
+
+        let mut count: usize = 0;
+        let mut next_x = isize::MIN;
+        for range in covered {
+            let start = (*range.start()).max(next_x);
+            if start <= *range.end() {
+                count += (range.end() - start + 1) as usize;
+                next_x = range.end() + 1;
+            }
+        }
+
+        let beacons_in_row = readings
+            .iter()
+            .map(|(_, beacon)| beacon)
+            .filter(|beacon| beacon.y == y)
+            .collect::<HashSet<_>>()
+            .len();
+
+        Ok(count - beacons_in_row)
+    }
+
+    fn part2(&self, readings: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let max_search = self.max_search;
+        let mut dead_rows: HashSet<isize> = HashSet::new();
+        let mut rows: HashMap<isize, Vec<RangeInclusive<isize>>> = HashMap::new();
+
+        for (sensor, beacon) in readings {
+            let sb_dist = sensor.manhattan(*beacon);
+            let min_y = (sensor.y - sb_dist).max(0);
+            let max_y = (sensor.y + sb_dist).min(max_search);
+            for y in min_y..=max_y {
+                if dead_rows.contains(&y) {
+                    continue;
+                }
+                let sy_dist = (sensor.y - y).abs();
+                let half_width = sb_dist - sy_dist;
+                let lower_x = (sensor.x - half_width).max(0);
+                let upper_x = (sensor.x + half_width).min(max_search);
+                if lower_x > 0 || upper_x < max_search {
+                    let inserted =
+                        merge_range(rows.entry(y).or_default(), &(lower_x..=upper_x), max_search);
+                    if !inserted {
+                        rows.remove(&y);
+                        dead_rows.insert(y);
+                    }
+                } else {
+                    dead_rows.insert(y);
+                    rows.remove(&y);
+                }
+            }
+        }
+
+        diagnostics::note("rows_with_gaps", rows.len());
+        diagnostics::note("dead_rows", dead_rows.len());
+
+        let (y, ranges) = rows
+            .iter()
+            .sorted_by_key(|(k, _)| *k)
+            .next()
+            .ok_or("No row with a gap")?;
+        let first_range = ranges.iter().min_by_key(|r| r.start()).ok_or("Empty row")?;
+        let x = if *first_range.start() > 0 {
+            0
+        } else {
+            first_range.end() + 1
+        };
+
+        Ok(x * 4000000 + y)
+    }
+}
+
+/// Checks the range merging in both parts against looking at every position
+/// one at a time.
+pub struct Reference;
+
+#[derive(Debug, Clone)]
+pub struct Case {
+    lines: Vec<String>,
+    target_row: isize,
+    max_search: isize,
+}
+
+impl Case {
+    fn solver(&self) -> Day15 {
+        Day15 {
+            target_row: self.target_row,
+            max_search: self.max_search,
+        }
+    }
+
+    fn readings(&self) -> Result<Vec<Reading>, InputError> {
+        Solution::parse(&self.solver(), &self.lines.join("\n"))
+    }
+
+    /// The puzzle promises exactly one spot in the search area that no sensor
+    /// can see.
+    fn is_valid(&self) -> bool {
+        match self.readings() {
+            Ok(readings) => uncovered(&readings, self.max_search).take(2).count() == 1,
+            Err(_) => false,
+        }
+    }
+}
+
+fn is_covered(readings: &[Reading], point: Point) -> bool {
+    readings
+        .iter()
+        .any(|(sensor, beacon)| sensor.manhattan(point) <= sensor.manhattan(*beacon))
+}
+
+/// Every point in the search area that no sensor covers, row by row.
+fn uncovered(readings: &[Reading], max_search: isize) -> impl Iterator<Item = Point> + '_ {
+    (0..=max_search)
+        .flat_map(move |y| (0..=max_search).map(move |x| Point::new(x, y)))
+        .filter(move |&p| !is_covered(readings, p))
+}
+
+impl Differential for Reference {
+    type Case = Case;
+    type Output = (usize, isize);
+
+    const DAY: usize = 15;
+    const NAME: &'static str = "sensor coverage";
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Case {
+        let max_search = 4 + 2 * size as isize;
+        let hidden = Point::new(rng.range(0, max_search), rng.range(0, max_search));
+
+        // Keep adding sensors that cover something new but stop just short of
+        // the hidden beacon, until it's the only spot left
+        let mut readings: Vec<Reading> = Vec::new();
+        loop {
+            let target = match uncovered(&readings, max_search).find(|&p| p != hidden) {
+                Some(target) => target,
+                None => break,
+            };
+            // Step straight away from the hidden beacon along one axis
+            let away = if target.x != hidden.x {
+                Point::new((target.x - hidden.x).signum(), 0)
+            } else {
+                Point::new(0, (target.y - hidden.y).signum())
+            };
+            let sensor = target + away * rng.range(1, 3);
+            let radius = sensor.manhattan(hidden) - 1;
+            let dx = rng.range(-radius, radius);
+            let dy = (radius - dx.abs()) * if rng.chance(1, 2) { 1 } else { -1 };
+            readings.push((sensor, sensor + Point::new(dx, dy)));
+        }
+
+        Case {
+            lines: readings
+                .iter()
+                .map(|(s, b)| {
+                    format!(
+                        "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                        s.x, s.y, b.x, b.y
+                    )
+                })
+                .collect(),
+            target_row: rng.range(-2, max_search + 2),
+            max_search,
+        }
+    }
+
+    fn shrink(&self, case: &Case) -> Vec<Case> {
+        (0..case.lines.len())
+            .map(|idx| {
+                let mut smaller = case.clone();
+                smaller.lines.remove(idx);
+                smaller
+            })
+            .filter(Case::is_valid)
+            .collect()
+    }
+
+    fn optimized(&self, case: &Case) -> Result<Self::Output, Box<dyn error::Error>> {
+        let solver = case.solver();
+        let readings = case.readings()?;
+        Ok((solver.part1(&readings)?, solver.part2(&readings)?))
+    }
+
+    fn reference(&self, case: &Case) -> Result<Self::Output, Box<dyn error::Error>> {
+        let readings = case.readings()?;
+        let beacons: HashSet<Point> = readings.iter().map(|&(_, beacon)| beacon).collect();
+
+        let reach = readings
+            .iter()
+            .map(|(sensor, beacon)| sensor.manhattan(*beacon))
+            .max()
+            .unwrap_or(0);
+        let min_x = readings.iter().map(|(s, _)| s.x).min().unwrap_or(0) - reach;
+        let max_x = readings.iter().map(|(s, _)| s.x).max().unwrap_or(0) + reach;
+        let part1 = (min_x..=max_x)
+            .map(|x| Point::new(x, case.target_row))
+            .filter(|&p| is_covered(&readings, p) && !beacons.contains(&p))
+            .count();
+
+        let gap = uncovered(&readings, case.max_search)
+            .next()
+            .ok_or("No uncovered spot")?;
+        Ok((part1, gap.x * 4000000 + gap.y))
+    }
+}
@@ -0,0 +1,323 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    error,
+    str::FromStr,
+};
+
+use crate::{
+    diagnostics, error::InputError, line_pattern, pathfinding::bfs, pattern::List,
+    solution::Solution, trace,
+};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Name {
+    c1: char,
+    c2: char,
+}
+
+impl std::fmt::Debug for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s: String = vec![self.c1, self.c2].into_iter().collect();
+        f.write_str(&s)
+    }
+}
+
+impl FromStr for Name {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Name, InputError> {
+        match s.chars().collect::<Vec<_>>()[..] {
+            [c1, c2] => Ok(Name { c1, c2 }),
+            _ => Err(InputError::malformed(format!(
+                "valve names are two letters, not {:?}",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Valve {
+    name: Name,
+    flow_rate: usize,
+    tunnels: HashSet<Name>,
+}
+
+line_pattern! {
+    struct ValveLine = r"Valve (\w+) has flow rate=(\d+); tunnels? leads? to valves? (.+)" {
+        name: Name,
+        flow_rate: usize,
+        tunnels: List<Name>,
+    }
+}
+
+/// The number of minutes it takes to walk from `start` to `end`.
+fn get_distance(valves: &HashMap<Name, Valve>, start: &Name, end: &Name) -> usize {
+    bfs(
+        Some(*start),
+        |name| valves[name].tunnels.iter().copied(),
+        |name| name == end,
+    )
+    // Walking somewhere unreachable will never fit in the time left
+    .map_or(usize::MAX, |path| path.cost)
+}
+
+fn get_flow_valve_distances(
+    initial_start: &Name,
+    valves: &HashMap<Name, Valve>,
+) -> HashMap<Name, HashMap<Name, usize>> {
+    let flow_valves: Vec<Name> = valves
+        .values()
+        .filter_map(|valve| {
+            if valve.name == *initial_start || valve.flow_rate > 0 {
+                Some(valve.name)
+            } else {
+                None
+            }
+        })
+        .sorted()
+        .collect();
+
+    let mut distances: HashMap<Name, HashMap<Name, usize>> = HashMap::new();
+
+    for (start_idx, &start) in flow_valves.iter().enumerate() {
+        // Every valve gets a table, even if there's nowhere worth going from
+        // it, as when the start is the only valve or none of them flow
+        distances.entry(start).or_default();
+        for &end in &flow_valves[start_idx + 1..] {
+            let distance = get_distance(valves, &start, &end);
+            let from_start_map = distances.entry(start).or_default();
+            from_start_map.insert(end, distance);
+            let from_end_map = distances.entry(end).or_default();
+            from_end_map.insert(start, distance);
+        }
+    }
+
+    distances
+}
+
+fn best_flow(
+    current_valve: &Name,
+    valves: &HashMap<Name, Valve>,
+    target_valves: &Vec<Name>,
+    flow_valve_distances: &HashMap<Name, HashMap<Name, usize>>,
+    remaining_minutes: usize,
+    activated_valves: &HashSet<Name>,
+) -> usize {
+    if remaining_minutes == 0 {
+        return 0;
+    }
+
+    let minute_flow: usize = activated_valves
+        .iter()
+        .map(|name| valves.get(name).unwrap().flow_rate)
+        .sum();
+
+    let wait_outcome = remaining_minutes * minute_flow;
+
+    let distances_from_here = flow_valve_distances.get(current_valve).unwrap();
+    let maybe_movement_outcome = target_valves
+        .iter()
+        .filter_map(|dest_name| {
+            if activated_valves.contains(dest_name) {
+                return None;
+            }
+
+            let distance = distances_from_here.get(dest_name).unwrap();
+            if *distance < remaining_minutes {
+                let time_elapsed = distance + 1;
+                let mut new_activated_valves = activated_valves.clone();
+                new_activated_valves.insert(dest_name.to_owned());
+                Some(
+                    best_flow(
+                        dest_name,
+                        valves,
+                        target_valves,
+                        flow_valve_distances,
+                        remaining_minutes - time_elapsed,
+                        &new_activated_valves,
+                    ) + time_elapsed * minute_flow,
+                )
+            } else {
+                None
+            }
+        })
+        .max();
+
+    match maybe_movement_outcome {
+        Some(n) if n > wait_outcome => n,
+        _ => wait_outcome,
+    }
+}
+
+fn all_partitions(valve_names: &[Name]) -> Vec<(Vec<Name>, Vec<Name>)> {
+    if valve_names.is_empty() {
+        return vec![];
+    }
+
+    let head = &valve_names[0];
+
+    if valve_names.len() == 1 {
+        return vec![(vec![*head], vec![]), (vec![], vec![*head])];
+    }
+
+    let rest_partitions = all_partitions(&valve_names[1..]);
+    rest_partitions
+        .iter()
+        .flat_map(|(a, b)| {
+            let mut extended_a = a.clone();
+            extended_a.push(*head);
+            let mut extended_b = b.clone();
+            extended_b.push(*head);
+            vec![(extended_a, b.clone()), (a.clone(), extended_b)]
+        })
+        .collect()
+}
+
+const START_VALVE: Name = Name { c1: 'A', c2: 'A' };
+
+#[derive(Serialize, Deserialize)]
+pub struct Day16 {
+    /// Minutes before the volcano erupts when working alone.
+    pub part1_minutes: usize,
+    /// What's left after teaching the elephant.
+    pub part2_minutes: usize,
+}
+
+impl Day16 {
+    pub const DEFAULT: Day16 = Day16 {
+        part1_minutes: 30,
+        part2_minutes: 26,
+    };
+}
+
+impl Solution for Day16 {
+    const DAY: usize = 16;
+
+    type Parsed = HashMap<Name, Valve>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let mut valves: HashMap<Name, Valve> = HashMap::new();
+        // Where each valve was described, for pointing at bad tunnels
+        let mut lines: HashMap<Name, (usize, &str)> = HashMap::new();
+
+        for (idx, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let parse_valve = || -> Result<Valve, InputError> {
+                let valve = ValveLine::parse_line(line)?;
+                Ok(Valve {
+                    name: valve.name,
+                    flow_rate: valve.flow_rate,
+                    tunnels: valve.tunnels.0.into_iter().collect(),
+                })
+            };
+            let valve = parse_valve().map_err(|e| e.at(idx + 1, line))?;
+            lines.insert(valve.name, (idx + 1, line));
+            valves.insert(valve.name, valve);
+        }
+
+        if !valves.contains_key(&START_VALVE) {
+            return Err(InputError::invalid_reference(format!(
+                "no valve named {:?}",
+                START_VALVE
+            )));
+        }
+        for valve in valves.values() {
+            if let Some(name) = valve.tunnels.iter().find(|n| !valves.contains_key(n)) {
+                let (number, line) = lines[&valve.name];
+                return Err(InputError::invalid_reference(format!(
+                    "tunnel to unknown valve {:?}",
+                    name
+                ))
+                .at(number, line));
+            }
+        }
+
+        Ok(valves)
+    }
+
+    fn part1(&self, valves: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let start_valve = START_VALVE;
+
+        let flow_valve_distances = get_flow_valve_distances(&start_valve, valves);
+
+        let flow_valve_names: Vec<Name> = flow_valve_distances
+            .keys()
+            .filter(|n| **n != start_valve)
+            .cloned()
+            .collect();
+        diagnostics::note("flow_valves", flow_valve_names.len());
+
+        Ok(best_flow(
+            &start_valve,
+            valves,
+            &flow_valve_names,
+            &flow_valve_distances,
+            self.part1_minutes,
+            &HashSet::new(),
+        ))
+    }
+
+    fn part2(&self, valves: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let start_valve = START_VALVE;
+
+        let flow_valve_distances = get_flow_valve_distances(&start_valve, valves);
+
+        let flow_valve_names: Vec<Name> = flow_valve_distances
+            .keys()
+            .filter(|n| **n != start_valve)
+            .cloned()
+            .collect();
+        diagnostics::note("flow_valves", flow_valve_names.len());
+        let mut partitions = all_partitions(&flow_valve_names[..]);
+        partitions.sort_by_cached_key(|(a, b)| {
+            let a_value: isize = a.iter().map(|x| valves[x].flow_rate as isize).sum();
+            let b_value: isize = b.iter().map(|x| valves[x].flow_rate as isize).sum();
+            (a_value - b_value).abs()
+        });
+
+        diagnostics::note("partitions", partitions.len());
+
+        let mut best_score = 0;
+        for (idx, (a_targets, b_targets)) in partitions.iter().enumerate() {
+            if idx % 100 == 0 {
+                trace!(
+                    "Day 16: partition {} of {}, best so far {}",
+                    idx,
+                    partitions.len(),
+                    best_score
+                );
+            }
+
+            let score = best_flow(
+                &start_valve,
+                valves,
+                a_targets,
+                &flow_valve_distances,
+                self.part2_minutes,
+                &HashSet::new(),
+            ) + best_flow(
+                &start_valve,
+                valves,
+                b_targets,
+                &flow_valve_distances,
+                self.part2_minutes,
+                &HashSet::new(),
+            );
+
+            if score > best_score {
+                best_score = score;
+            }
+        }
+
+        Ok(best_score)
+    }
+}
@@ -0,0 +1,376 @@
+use std::{collections::HashSet, error, fmt::Debug, rc::Rc};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cycle::{extrapolate, OutOfRange},
+    diagnostics,
+    differential::{Differential, Rng},
+    error::InputError,
+    grid::Grid,
+    solution::Solution,
+    viewer::Simulation,
+};
+
+#[derive(Clone, Copy)]
+pub enum Push {
+    Left,
+    Right,
+}
+
+impl Debug for Push {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left => write!(f, "<"),
+            Self::Right => write!(f, ">"),
+        }
+    }
+}
+
+type Shape = Grid<bool>;
+
+const WIDTH: usize = 7;
+
+/// Parses a rock drawn the way the puzzle shows it, flipping it so that row 0
+/// is the bottom like in the chamber.
+fn parse_shape(picture: &str) -> Shape {
+    let rows: Vec<Vec<bool>> = picture
+        .lines()
+        .rev()
+        .map(|line| line.chars().map(|c| c == '#').collect())
+        .collect();
+    Grid::from_rows(rows).unwrap()
+}
+
+/// The positions a shape covers when its bottom left corner is at `(x, y)`.
+fn shape_cells(shape: &Shape, x: isize, y: isize) -> impl Iterator<Item = (isize, isize)> + '_ {
+    shape
+        .iter()
+        .filter(|(_, &filled)| filled)
+        .map(move |((dx, dy), _)| (x + dx as isize, y + dy as isize))
+}
+
+fn is_intersect(shape: &Shape, x: isize, y: isize, grid: &Grid<bool>) -> bool {
+    shape_cells(shape, x, y).any(|(cx, cy)| {
+        cx < 0 || cx >= WIDTH as isize || grid.get(cx, cy).copied().unwrap_or(false)
+    })
+}
+
+fn insert_shape(shape: &Shape, x: isize, y: isize, grid: &mut Grid<bool>) {
+    for (cx, cy) in shape_cells(shape, x, y) {
+        while grid.height() as isize <= cy {
+            grid.push_row(vec![false; WIDTH]).unwrap();
+        }
+        *grid.get_mut(cx, cy).unwrap() = true;
+    }
+}
+
+/// How far down from the top of the tower to look for space a rock could fall
+/// into before giving up on comparing that state to others.
+const MAX_SNAPSHOT_DEPTH: isize = 64;
+
+#[derive(Clone)]
+struct Chamber {
+    shapes: Rc<[Shape]>,
+    pushes: Rc<[Push]>,
+    grid: Grid<bool>,
+    shape_idx: usize,
+    push_idx: usize,
+}
+
+/// Everything that decides how the rest of the tower will be built: which
+/// rock and jet come next, and the shape of the top of the tower.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Snapshot {
+    shape_idx: usize,
+    push_idx: usize,
+    /// The empty cells a falling rock could still get to, as (x, depth below
+    /// the top of the tower). Everything else might as well be solid rock.
+    open: Vec<(usize, usize)>,
+}
+
+impl Chamber {
+    fn new(pushes: &[Push]) -> Self {
+        Chamber {
+            shapes: rock_shapes().into(),
+            pushes: pushes.into(),
+            grid: Grid::new(WIDTH, 0, false),
+            shape_idx: 0,
+            push_idx: 0,
+        }
+    }
+
+    /// `None` if rocks could still fall deeper than `MAX_SNAPSHOT_DEPTH`.
+    fn snapshot(&self) -> Option<Snapshot> {
+        let height = self.grid.height() as isize;
+        let is_open = |(x, y): (isize, isize)| {
+            (0..WIDTH as isize).contains(&x)
+                && y >= 0
+                && !self.grid.get(x, y).copied().unwrap_or(false)
+        };
+
+        // Flood down from the empty row above the tower; rocks never move up,
+        // so nothing they can reach is missed
+        let mut seen: HashSet<(isize, isize)> = (0..WIDTH as isize).map(|x| (x, height)).collect();
+        let mut stack: Vec<(isize, isize)> = seen.iter().copied().collect();
+        while let Some((x, y)) = stack.pop() {
+            if height - y > MAX_SNAPSHOT_DEPTH {
+                return None;
+            }
+            for next in [(x - 1, y), (x + 1, y), (x, y - 1)].iter().copied() {
+                if is_open(next) && seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        let mut open: Vec<(usize, usize)> = seen
+            .into_iter()
+            .map(|(x, y)| (x as usize, (height - y) as usize))
+            .collect();
+        open.sort_unstable();
+
+        Some(Snapshot {
+            shape_idx: self.shape_idx,
+            push_idx: self.push_idx,
+            open,
+        })
+    }
+
+    fn drop_rock(&mut self) {
+        let shape = &self.shapes[self.shape_idx];
+        let mut x: isize = 2;
+        let mut y: isize = self.grid.height() as isize + 3;
+
+        if is_intersect(shape, x, y, &self.grid) {
+            panic!("Intersection at start");
+        }
+
+        loop {
+            let new_x = match self.pushes[self.push_idx] {
+                Push::Right => x + 1,
+                Push::Left => x - 1,
+            };
+
+            if !is_intersect(shape, new_x, y, &self.grid) {
+                x = new_x;
+            }
+            self.push_idx = (self.push_idx + 1) % self.pushes.len();
+
+            if y == 0 || is_intersect(shape, x, y - 1, &self.grid) {
+                break;
+            }
+            y -= 1;
+        }
+
+        insert_shape(shape, x, y, &mut self.grid);
+        self.shape_idx = (self.shape_idx + 1) % self.shapes.len();
+    }
+}
+
+fn rock_shapes() -> Vec<Shape> {
+    [
+        "####",
+        ".#.\n###\n.#.",
+        "..#\n..#\n###",
+        "#\n#\n#\n#",
+        "##\n##",
+    ]
+    .iter()
+    .map(|picture| parse_shape(picture))
+    .collect()
+}
+
+/// Rocks falling into the chamber one per step, until `rocks` have come to
+/// rest.
+#[derive(Clone)]
+struct Falling {
+    chamber: Chamber,
+    dropped: usize,
+    rocks: usize,
+}
+
+impl Simulation for Falling {
+    fn step(&mut self) -> bool {
+        if self.dropped == self.rocks {
+            return false;
+        }
+        self.chamber.drop_rock();
+        self.dropped += 1;
+        true
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "{} of {} rocks at rest, tower height {}",
+            self.dropped,
+            self.rocks,
+            self.chamber.grid.height()
+        )
+    }
+
+    /// The tower from the top down, between the chamber walls.
+    fn rows(&self) -> Vec<String> {
+        self.chamber
+            .grid
+            .rows()
+            .rev()
+            .map(|row| {
+                format!(
+                    "|{}|",
+                    row.iter().map(|&b| if b { '#' } else { '.' }).join("")
+                )
+            })
+            .chain(std::iter::once(format!("+{}+", "-".repeat(WIDTH))))
+            .collect()
+    }
+
+    /// The floor stays put as the tower grows upwards.
+    fn origin(&self) -> (isize, isize) {
+        (0, -(self.chamber.grid.height() as isize))
+    }
+}
+
+fn tower_height(pushes: &[Push], rocks_to_insert: usize) -> Result<usize, OutOfRange> {
+    let height = extrapolate(
+        Chamber::new(pushes),
+        rocks_to_insert,
+        Chamber::snapshot,
+        |chamber| chamber.grid.height(),
+        Chamber::drop_rock,
+    )?;
+    diagnostics::note("cycle", height.cycle);
+    Ok(height.value)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day17 {
+    /// How many rocks fall before measuring the tower, in each part.
+    pub part1_rocks: usize,
+    pub part2_rocks: usize,
+}
+
+impl Day17 {
+    pub const DEFAULT: Day17 = Day17 {
+        part1_rocks: 2022,
+        part2_rocks: 1_000_000_000_000,
+    };
+}
+
+impl Solution for Day17 {
+    const DAY: usize = 17;
+
+    type Parsed = Vec<Push>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let mut pushes: Vec<Push> = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            for c in line.chars() {
+                pushes.push(match c {
+                    '>' => Push::Right,
+                    '<' => Push::Left,
+                    _ => return Err(InputError::unknown_token(c).at(idx + 1, line)),
+                });
+            }
+        }
+        diagnostics::note("pushes", pushes.len());
+
+        if pushes.is_empty() {
+            return Err(InputError::malformed("no jet pattern"));
+        }
+
+        Ok(pushes)
+    }
+
+    fn part1(&self, pushes: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(tower_height(pushes, self.part1_rocks)?)
+    }
+
+    fn part2(&self, pushes: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        Ok(tower_height(pushes, self.part2_rocks)?)
+    }
+
+    fn simulation(&self, pushes: &Self::Parsed, part: usize) -> Option<Box<dyn Simulation>> {
+        let rocks = if part == 1 {
+            self.part1_rocks
+        } else {
+            self.part2_rocks
+        };
+        Some(Box::new(Falling {
+            chamber: Chamber::new(pushes),
+            dropped: 0,
+            rocks,
+        }))
+    }
+}
+
+/// Checks the cycle skip in `tower_height` against dropping every rock.
+pub struct Reference;
+
+#[derive(Debug, Clone)]
+pub struct Case {
+    jets: String,
+    rocks: usize,
+}
+
+impl Case {
+    fn pushes(&self) -> Result<Vec<Push>, InputError> {
+        Day17::DEFAULT.parse(&self.jets)
+    }
+}
+
+impl Differential for Reference {
+    type Case = Case;
+    type Output = usize;
+
+    const DAY: usize = 17;
+    const NAME: &'static str = "tower height";
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Case {
+        let jets = (0..1 + rng.index(5 * size))
+            .map(|_| if rng.chance(1, 2) { '<' } else { '>' })
+            .collect();
+        Case {
+            jets,
+            rocks: rng.index(300 * size),
+        }
+    }
+
+    fn shrink(&self, case: &Case) -> Vec<Case> {
+        let mut smaller = Vec::new();
+        if case.rocks > 0 {
+            for rocks in [case.rocks / 2, case.rocks - 1].iter() {
+                smaller.push(Case {
+                    rocks: *rocks,
+                    ..case.clone()
+                });
+            }
+        }
+        if case.jets.len() > 1 {
+            for idx in 0..case.jets.len() {
+                let mut jets = case.jets.clone();
+                jets.remove(idx);
+                smaller.push(Case {
+                    jets,
+                    ..case.clone()
+                });
+            }
+        }
+        smaller
+    }
+
+    fn optimized(&self, case: &Case) -> Result<usize, Box<dyn error::Error>> {
+        Ok(tower_height(&case.pushes()?, case.rocks)?)
+    }
+
+    fn reference(&self, case: &Case) -> Result<usize, Box<dyn error::Error>> {
+        let mut chamber = Chamber::new(&case.pushes()?);
+        for _ in 0..case.rocks {
+            chamber.drop_rock();
+        }
+        Ok(chamber.grid.height())
+    }
+}
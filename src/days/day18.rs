@@ -0,0 +1,130 @@
+use std::{collections::HashSet, error};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::InputError,
+    export::{Image, Rgb},
+    geometry::{BoundingBox3, Point3},
+    input::parse_lines,
+    solution::Solution,
+};
+
+type Point = Point3<isize>;
+
+/// A box one larger than the droplet on every side, so the steam can get all
+/// around it, and every point in the box that steam from outside reaches.
+/// `None` if there are no cubes.
+fn steam(points: &HashSet<Point>) -> Option<(BoundingBox3<isize>, HashSet<Point>)> {
+    // Flood fill from a corner of the box, which is outside the droplet
+    let bounds = BoundingBox3::from_points(points.iter().copied())?.expand(1);
+
+    let mut external_points: HashSet<Point> = HashSet::new();
+    let mut to_add: HashSet<Point> = HashSet::new();
+    let mut to_add_next: HashSet<Point> = HashSet::new();
+
+    to_add.insert(bounds.min);
+
+    while !to_add.is_empty() {
+        for point in to_add.iter() {
+            for p2 in point.neighbours6() {
+                if points.contains(&p2)
+                    || external_points.contains(&p2)
+                    || to_add.contains(&p2)
+                    || !bounds.contains(p2)
+                {
+                    continue;
+                }
+
+                to_add_next.insert(p2);
+            }
+        }
+
+        external_points.extend(to_add.drain());
+        to_add = to_add_next;
+        to_add_next = HashSet::new();
+    }
+
+    Some((bounds, external_points))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: usize = 18;
+
+    type Parsed = HashSet<Point>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        parse_lines(input, |line| {
+            let nums: Vec<isize> = line
+                .split(',')
+                .map(|s| s.parse())
+                .collect::<Result<_, _>>()?;
+            if nums.len() != 3 {
+                return Err(InputError::malformed("expected three coordinates"));
+            }
+            Ok(Point::new(nums[0], nums[1], nums[2]))
+        })
+    }
+
+    fn part1(&self, points: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(points
+            .iter()
+            .map(|p| p.neighbours6().filter(|p2| !points.contains(p2)).count())
+            .sum())
+    }
+
+    fn part2(&self, points: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let (_, external_points) = steam(points).ok_or("No cubes")?;
+
+        let surface_area: usize = points
+            .iter()
+            .map(|p| {
+                p.neighbours6()
+                    .filter(|p2| external_points.contains(p2))
+                    .count()
+            })
+            .sum();
+
+        Ok(surface_area)
+    }
+
+    /// A slice through the droplet for each layer, from the lowest z up, with
+    /// the cubes in brown. Part 2 also shows the steam around the droplet in
+    /// blue, so that the air pockets trapped inside it stand out in black.
+    fn pictures(&self, points: &Self::Parsed, part: usize) -> Option<Vec<Image>> {
+        let (bounds, external_points) = steam(points)?;
+        let slices = (bounds.min.z..=bounds.max.z)
+            .map(|z| {
+                let width = (bounds.max.x - bounds.min.x + 1) as usize;
+                let height = (bounds.max.y - bounds.min.y + 1) as usize;
+                let mut image = Image::new(width, height, Rgb::WHITE);
+                for y in bounds.min.y..=bounds.max.y {
+                    for x in bounds.min.x..=bounds.max.x {
+                        let p = Point::new(x, y, z);
+                        let color = if points.contains(&p) {
+                            Rgb(140, 80, 40)
+                        } else if part == 1 {
+                            continue;
+                        } else if external_points.contains(&p) {
+                            Rgb(170, 210, 250)
+                        } else {
+                            Rgb::BLACK
+                        };
+                        image.set(
+                            (x - bounds.min.x) as usize,
+                            (y - bounds.min.y) as usize,
+                            color,
+                        );
+                    }
+                }
+                image
+            })
+            .collect();
+        Some(slices)
+    }
+}
@@ -0,0 +1,417 @@
+use std::{collections::HashMap, error};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    differential::{Differential, Rng},
+    error::InputError,
+    input::parse_lines,
+    line_pattern,
+    solution::Solution,
+    trace,
+};
+
+line_pattern! {
+    #[derive(Debug, Clone)]
+    pub struct Blueprint =
+        r"Blueprint (\d+): Each ore robot costs (\d+) ore\. Each clay robot costs (\d+) ore\. Each obsidian robot costs (\d+) ore and (\d+) clay\. Each geode robot costs (\d+) ore and (\d+) obsidian\." {
+        id: u16,
+        ore_robot_ore_cost: u16,
+        clay_robot_ore_cost: u16,
+        obsidian_robot_ore_cost: u16,
+        obsidian_robot_clay_cost: u16,
+        geode_robot_ore_cost: u16,
+        geode_robot_obsidian_cost: u16,
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+struct State {
+    ore: u16,
+    clay: u16,
+    obsidian: u16,
+    open_geodes: u16,
+    ore_robots: u8,
+    clay_robots: u8,
+    obsidian_robots: u8,
+    geode_robots: u8,
+}
+
+impl State {
+    fn new() -> State {
+        State {
+            ore_robots: 1,
+            ..State::default()
+        }
+    }
+
+    fn wait(&self) -> State {
+        State {
+            ore: self.ore + self.ore_robots as u16,
+            clay: self.clay + self.clay_robots as u16,
+            obsidian: self.obsidian + self.obsidian_robots as u16,
+            open_geodes: self.open_geodes + self.geode_robots as u16,
+            ..*self
+        }
+    }
+
+    fn build_ore_robot(&self, state_after_wait: &State, blueprint: &Blueprint) -> Option<State> {
+        if self.ore < blueprint.ore_robot_ore_cost {
+            return None;
+        }
+
+        Some(State {
+            ore_robots: self.ore_robots + 1,
+            ore: state_after_wait.ore - blueprint.ore_robot_ore_cost,
+            ..*state_after_wait
+        })
+    }
+
+    fn build_clay_robot(&self, state_after_wait: &State, blueprint: &Blueprint) -> Option<State> {
+        if self.ore < blueprint.clay_robot_ore_cost {
+            return None;
+        }
+
+        Some(State {
+            clay_robots: self.clay_robots + 1,
+            ore: state_after_wait.ore - blueprint.clay_robot_ore_cost,
+            ..*state_after_wait
+        })
+    }
+
+    fn build_obsidian_robot(
+        &self,
+        state_after_wait: &State,
+        blueprint: &Blueprint,
+    ) -> Option<State> {
+        if self.ore < blueprint.obsidian_robot_ore_cost {
+            return None;
+        }
+        if self.clay < blueprint.obsidian_robot_clay_cost {
+            return None;
+        }
+
+        Some(State {
+            obsidian_robots: self.obsidian_robots + 1,
+            ore: state_after_wait.ore - blueprint.obsidian_robot_ore_cost,
+            clay: state_after_wait.clay - blueprint.obsidian_robot_clay_cost,
+            ..*state_after_wait
+        })
+    }
+
+    fn build_geode_robot(&self, state_after_wait: &State, blueprint: &Blueprint) -> Option<State> {
+        if self.ore < blueprint.geode_robot_ore_cost {
+            return None;
+        }
+        if self.obsidian < blueprint.geode_robot_obsidian_cost {
+            return None;
+        }
+
+        Some(State {
+            geode_robots: self.geode_robots + 1,
+            ore: state_after_wait.ore - blueprint.geode_robot_ore_cost,
+            obsidian: state_after_wait.obsidian - blueprint.geode_robot_obsidian_cost,
+            ..*state_after_wait
+        })
+    }
+}
+
+fn is_benefit_from_building_robot(
+    current_robots: u16,
+    maximum_useful_output: u16,
+    current_stockpile: u16,
+    minutes_remaining: u16,
+) -> bool {
+    if current_robots >= maximum_useful_output {
+        return false;
+    }
+
+    if current_stockpile + (current_robots * minutes_remaining)
+        >= maximum_useful_output * minutes_remaining
+    {
+        return false;
+    }
+
+    true
+}
+
+/// The most geodes this state could possibly end up with, assuming a new geode
+/// robot gets built every remaining minute.
+fn geode_upper_bound(state: &State, minutes_remaining: u16) -> usize {
+    let t = minutes_remaining as usize;
+    state.open_geodes as usize + state.geode_robots as usize * t + t * t.saturating_sub(1) / 2
+}
+
+fn find_maximum_geodes(state: &State, blueprint: &Blueprint, minutes_remaining: u16) -> usize {
+    let geodes = search_geodes(state, blueprint, minutes_remaining, &mut 0);
+    trace!(
+        "Day 19: blueprint {} opens {} geodes in {} minutes",
+        blueprint.id,
+        geodes,
+        minutes_remaining
+    );
+    geodes
+}
+
+fn search_geodes(
+    state: &State,
+    blueprint: &Blueprint,
+    minutes_remaining: u16,
+    best_found: &mut usize,
+) -> usize {
+    if minutes_remaining == 0 {
+        *best_found = (*best_found).max(state.open_geodes as usize);
+        return state.open_geodes as usize;
+    }
+
+    if geode_upper_bound(state, minutes_remaining) <= *best_found {
+        return 0;
+    }
+
+    let mut best_score: usize = 0;
+    let mut maximum_useful_ore_cost = blueprint.geode_robot_ore_cost;
+    let state_after_wait = state.wait();
+
+    if let Some(geode_robot_state) = state.build_geode_robot(&state_after_wait, blueprint) {
+        best_score = best_score.max(search_geodes(
+            &geode_robot_state,
+            blueprint,
+            minutes_remaining - 1,
+            best_found,
+        ));
+    } else {
+        // If we can't build a geode robot, try just waiting
+        best_score = best_score.max(search_geodes(
+            &state_after_wait,
+            blueprint,
+            minutes_remaining - 1,
+            best_found,
+        ));
+    }
+
+    if is_benefit_from_building_robot(
+        state.obsidian_robots.into(),
+        blueprint.geode_robot_obsidian_cost,
+        state.obsidian,
+        minutes_remaining,
+    ) {
+        maximum_useful_ore_cost = maximum_useful_ore_cost.max(blueprint.obsidian_robot_ore_cost);
+
+        if let Some(obsidian_robot_state) = state.build_obsidian_robot(&state_after_wait, blueprint)
+        {
+            best_score = best_score.max(search_geodes(
+                &obsidian_robot_state,
+                blueprint,
+                minutes_remaining - 1,
+                best_found,
+            ));
+        }
+
+        if is_benefit_from_building_robot(
+            state.clay_robots.into(),
+            blueprint.obsidian_robot_clay_cost,
+            state.clay,
+            minutes_remaining,
+        ) {
+            maximum_useful_ore_cost = maximum_useful_ore_cost.max(blueprint.clay_robot_ore_cost);
+
+            if let Some(clay_robot_state) = state.build_clay_robot(&state_after_wait, blueprint) {
+                best_score = best_score.max(search_geodes(
+                    &clay_robot_state,
+                    blueprint,
+                    minutes_remaining - 1,
+                    best_found,
+                ));
+            }
+        }
+    }
+
+    if is_benefit_from_building_robot(
+        state.ore_robots.into(),
+        maximum_useful_ore_cost,
+        state.ore,
+        minutes_remaining,
+    ) {
+        if let Some(ore_robot_state) = state.build_ore_robot(&state_after_wait, blueprint) {
+            best_score = best_score.max(search_geodes(
+                &ore_robot_state,
+                blueprint,
+                minutes_remaining - 1,
+                best_found,
+            ));
+        }
+    }
+
+    best_score
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day19 {
+    pub part1_minutes: u16,
+    pub part2_minutes: u16,
+    /// How many blueprints survive the elephants in part 2.
+    pub part2_blueprints: usize,
+}
+
+impl Day19 {
+    pub const DEFAULT: Day19 = Day19 {
+        part1_minutes: 24,
+        part2_minutes: 32,
+        part2_blueprints: 3,
+    };
+}
+
+impl Solution for Day19 {
+    const DAY: usize = 19;
+
+    type Parsed = Vec<Blueprint>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        parse_lines(input, Blueprint::parse_line)
+    }
+
+    fn part1(&self, blueprints: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let score: usize = blueprints
+            .par_iter()
+            .map(|blueprint| {
+                find_maximum_geodes(&State::new(), blueprint, self.part1_minutes)
+                    * (blueprint.id as usize)
+            })
+            .sum();
+
+        Ok(score)
+    }
+
+    fn part2(&self, blueprints: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let score: usize = blueprints
+            .par_iter()
+            .take(self.part2_blueprints)
+            .map(|blueprint| find_maximum_geodes(&State::new(), blueprint, self.part2_minutes))
+            .product();
+
+        Ok(score)
+    }
+}
+
+/// Checks the pruning in `search_geodes` against trying every choice every
+/// minute.
+pub struct Reference;
+
+fn naive_geodes(
+    state: &State,
+    blueprint: &Blueprint,
+    minutes_remaining: u16,
+    known: &mut HashMap<(State, u16), usize>,
+) -> usize {
+    if minutes_remaining == 0 {
+        return state.open_geodes as usize;
+    }
+    if let Some(&geodes) = known.get(&(state.clone(), minutes_remaining)) {
+        return geodes;
+    }
+
+    let after_wait = state.wait();
+    let choices = vec![
+        state.build_ore_robot(&after_wait, blueprint),
+        state.build_clay_robot(&after_wait, blueprint),
+        state.build_obsidian_robot(&after_wait, blueprint),
+        state.build_geode_robot(&after_wait, blueprint),
+        Some(after_wait),
+    ];
+    let geodes = choices
+        .into_iter()
+        .flatten()
+        .map(|next| naive_geodes(&next, blueprint, minutes_remaining - 1, known))
+        .max()
+        .unwrap();
+
+    known.insert((state.clone(), minutes_remaining), geodes);
+    geodes
+}
+
+#[derive(Debug, Clone)]
+pub struct Case {
+    blueprint: Blueprint,
+    minutes: u16,
+}
+
+impl Differential for Reference {
+    type Case = Case;
+    type Output = usize;
+
+    const DAY: usize = 19;
+    const NAME: &'static str = "geode search";
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Case {
+        let mut cost = |max| rng.range(1, max) as u16;
+        Case {
+            blueprint: Blueprint {
+                id: 1,
+                ore_robot_ore_cost: cost(4),
+                clay_robot_ore_cost: cost(4),
+                obsidian_robot_ore_cost: cost(4),
+                obsidian_robot_clay_cost: cost(8),
+                geode_robot_ore_cost: cost(4),
+                geode_robot_obsidian_cost: cost(8),
+            },
+            minutes: 4 + rng.index(size + 1) as u16,
+        }
+    }
+
+    fn shrink(&self, case: &Case) -> Vec<Case> {
+        let mut smaller = Vec::new();
+        if case.minutes > 1 {
+            smaller.push(Case {
+                minutes: case.minutes - 1,
+                ..case.clone()
+            });
+        }
+
+        let b = &case.blueprint;
+        let costs = [
+            b.ore_robot_ore_cost,
+            b.clay_robot_ore_cost,
+            b.obsidian_robot_ore_cost,
+            b.obsidian_robot_clay_cost,
+            b.geode_robot_ore_cost,
+            b.geode_robot_obsidian_cost,
+        ];
+        for idx in (0..costs.len()).filter(|&idx| costs[idx] > 1) {
+            let mut c = costs;
+            c[idx] -= 1;
+            smaller.push(Case {
+                blueprint: Blueprint {
+                    id: b.id,
+                    ore_robot_ore_cost: c[0],
+                    clay_robot_ore_cost: c[1],
+                    obsidian_robot_ore_cost: c[2],
+                    obsidian_robot_clay_cost: c[3],
+                    geode_robot_ore_cost: c[4],
+                    geode_robot_obsidian_cost: c[5],
+                },
+                ..case.clone()
+            });
+        }
+        smaller
+    }
+
+    fn optimized(&self, case: &Case) -> Result<usize, Box<dyn error::Error>> {
+        Ok(find_maximum_geodes(
+            &State::new(),
+            &case.blueprint,
+            case.minutes,
+        ))
+    }
+
+    fn reference(&self, case: &Case) -> Result<usize, Box<dyn error::Error>> {
+        Ok(naive_geodes(
+            &State::new(),
+            &case.blueprint,
+            case.minutes,
+            &mut HashMap::new(),
+        ))
+    }
+}
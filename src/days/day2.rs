@@ -0,0 +1,153 @@
+use std::error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::InputError, input::parse_lines, solution::Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Choice {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+static CHOICES: [Choice; 3] = [Choice::Rock, Choice::Paper, Choice::Scissors];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Outcome {
+    Win,
+    Tie,
+    Loss,
+}
+
+impl Choice {
+    fn from_abc(s: char) -> Option<Choice> {
+        match s {
+            'A' => Some(Choice::Rock),
+            'B' => Some(Choice::Paper),
+            'C' => Some(Choice::Scissors),
+            _ => None,
+        }
+    }
+
+    fn from_xyz(s: char) -> Option<Choice> {
+        match s {
+            'X' => Some(Choice::Rock),
+            'Y' => Some(Choice::Paper),
+            'Z' => Some(Choice::Scissors),
+            _ => None,
+        }
+    }
+
+    fn points(&self) -> usize {
+        match self {
+            Choice::Rock => 1,
+            Choice::Paper => 2,
+            Choice::Scissors => 3,
+        }
+    }
+
+    fn compare(&self, other: &Choice) -> Outcome {
+        use Choice::*;
+        use Outcome::*;
+
+        match self {
+            Rock => match other {
+                Rock => Tie,
+                Paper => Loss,
+                Scissors => Win,
+            },
+            Paper => match other {
+                Rock => Win,
+                Paper => Tie,
+                Scissors => Loss,
+            },
+            Scissors => match other {
+                Rock => Loss,
+                Paper => Win,
+                Scissors => Tie,
+            },
+        }
+    }
+}
+
+impl Outcome {
+    fn points(&self) -> usize {
+        match self {
+            Outcome::Loss => 0,
+            Outcome::Tie => 3,
+            Outcome::Win => 6,
+        }
+    }
+
+    fn from_xyz(s: char) -> Option<Outcome> {
+        match s {
+            'X' => Some(Outcome::Loss),
+            'Y' => Some(Outcome::Tie),
+            'Z' => Some(Outcome::Win),
+            _ => None,
+        }
+    }
+
+    fn my_choice_for_outcome(&self, them: &Choice) -> Choice {
+        *(CHOICES
+            .iter()
+            .find(|&me| me.compare(them) == *self)
+            .unwrap())
+    }
+}
+
+/// One line of the strategy guide. The meaning of the second column differs
+/// between the two parts, so it's kept as the raw `X`/`Y`/`Z` character.
+pub struct Round {
+    them: Choice,
+    xyz: char,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: usize = 2;
+
+    type Parsed = Vec<Round>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        parse_lines(input, |line| {
+            let them = line
+                .chars()
+                .next()
+                .and_then(Choice::from_abc)
+                .ok_or_else(|| InputError::malformed("bad opponent choice"))?;
+            let xyz = line
+                .chars()
+                .nth(2)
+                .filter(|c| Choice::from_xyz(*c).is_some())
+                .ok_or_else(|| InputError::malformed("bad response"))?;
+            Ok(Round { them, xyz })
+        })
+    }
+
+    fn part1(&self, rounds: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(rounds
+            .iter()
+            .map(|round| {
+                let me = Choice::from_xyz(round.xyz).unwrap();
+                me.points() + me.compare(&round.them).points()
+            })
+            .sum())
+    }
+
+    fn part2(&self, rounds: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        Ok(rounds
+            .iter()
+            .map(|round| {
+                let outcome = Outcome::from_xyz(round.xyz).unwrap();
+                let me = outcome.my_choice_for_outcome(&round.them);
+                me.points() + outcome.points()
+            })
+            .sum())
+    }
+}
@@ -0,0 +1,199 @@
+use std::error;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    checked,
+    differential::{Differential, Rng},
+    error::InputError,
+    input::parse_lines,
+    solution::Solution,
+};
+
+#[derive(Debug, Clone, Copy)]
+struct Item {
+    value: isize,
+    original_position: isize,
+}
+
+fn grove_coordinates(
+    values: &[isize],
+    key: isize,
+    rounds: usize,
+) -> Result<isize, Box<dyn error::Error>> {
+    let mut items: Vec<Item> = values
+        .iter()
+        .enumerate()
+        .map(|(idx, &value)| {
+            Ok(Item {
+                value: checked::mul(Day20::DAY, "decryption key", value, key)?,
+                original_position: idx as isize,
+            })
+        })
+        .collect::<Result<_, checked::Overflow>>()?;
+
+    let full_len = items.len();
+
+    for _ in 0..rounds {
+        for idx in 0..full_len {
+            let (old_pos, _) = items
+                .iter()
+                .find_position(|item| item.original_position == idx as isize)
+                .unwrap();
+
+            let item = items.remove(old_pos);
+            let mut new_pos = (old_pos as isize) + item.value;
+            if new_pos <= 0 {
+                let x = (-new_pos) / ((full_len as isize) - 1) + 1;
+                new_pos += ((full_len as isize) - 1) * x;
+            }
+            if new_pos >= full_len as isize {
+                let x = new_pos / ((full_len as isize) - 1);
+                new_pos -= ((full_len as isize) - 1) * x;
+            }
+            items.insert(new_pos as usize, item);
+        }
+    }
+
+    let (zero_pos, _) = items
+        .iter()
+        .find_position(|i| i.value == 0)
+        .ok_or("No zero value")?;
+    let coordinates = [1000, 2000, 3000]
+        .iter()
+        .map(|offset| items[(offset + zero_pos) % items.len()].value);
+
+    Ok(checked::sum(Day20::DAY, "grove coordinates", coordinates)?)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day20 {
+    pub decryption_key: isize,
+    /// How many times the numbers are mixed in part 2.
+    pub mix_rounds: usize,
+}
+
+impl Day20 {
+    pub const DEFAULT: Day20 = Day20 {
+        decryption_key: 811589153,
+        mix_rounds: 10,
+    };
+}
+
+impl Solution for Day20 {
+    const DAY: usize = 20;
+
+    /// The encrypted file's numbers in their original order.
+    type Parsed = Vec<isize>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let values: Vec<isize> = parse_lines(input, |line| Ok(line.parse()?))?;
+        // Mixing moves each number around the others, so there have to be some
+        if values.len() < 2 {
+            return Err(InputError::malformed("expected at least two numbers"));
+        }
+        Ok(values)
+    }
+
+    fn part1(&self, values: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        grove_coordinates(values, 1, 1)
+    }
+
+    fn part2(&self, values: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        grove_coordinates(values, self.decryption_key, self.mix_rounds)
+    }
+}
+
+/// Checks the wraparound arithmetic in `grove_coordinates` against moving
+/// each number one swap at a time.
+pub struct Reference;
+
+fn naive_grove_coordinates(values: &[isize], key: isize, rounds: usize) -> isize {
+    let len = values.len();
+    // Each number tagged with where it started
+    let mut ring: Vec<(usize, isize)> = values.iter().map(|v| v * key).enumerate().collect();
+
+    for _ in 0..rounds {
+        for original in 0..len {
+            let mut pos = ring.iter().position(|&(i, _)| i == original).unwrap();
+            // Going all the way around past the other numbers puts this one
+            // back where it started
+            let steps = ring[pos].1.rem_euclid(len as isize - 1);
+            for _ in 0..steps {
+                let next = (pos + 1) % len;
+                ring.swap(pos, next);
+                pos = next;
+            }
+        }
+    }
+
+    let zero = ring.iter().position(|&(_, v)| v == 0).unwrap();
+    [1000, 2000, 3000]
+        .iter()
+        .map(|offset| ring[(zero + offset) % len].1)
+        .sum()
+}
+
+impl Differential for Reference {
+    /// The encrypted file, which always has exactly one zero.
+    type Case = Vec<isize>;
+    type Output = (isize, isize);
+
+    const DAY: usize = 20;
+    const NAME: &'static str = "mixing";
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<isize> {
+        let len = 2 + rng.index(size + 1);
+        let limit = 3 * len as isize;
+        let mut values: Vec<isize> = (0..len)
+            .map(|_| match rng.range(-limit, limit) {
+                0 => 1,
+                v => v,
+            })
+            .collect();
+        values[rng.index(len)] = 0;
+        values
+    }
+
+    fn shrink(&self, values: &Vec<isize>) -> Vec<Vec<isize>> {
+        let mut smaller = Vec::new();
+        if values.len() > 2 {
+            for idx in (0..values.len()).filter(|&idx| values[idx] != 0) {
+                let mut fewer = values.clone();
+                fewer.remove(idx);
+                smaller.push(fewer);
+            }
+        }
+        for idx in (0..values.len()).filter(|&idx| values[idx].abs() > 1) {
+            let mut closer = values.clone();
+            closer[idx] -= closer[idx].signum();
+            smaller.push(closer);
+        }
+        smaller
+    }
+
+    fn optimized(&self, values: &Vec<isize>) -> Result<Self::Output, Box<dyn error::Error>> {
+        Ok((
+            grove_coordinates(values, 1, 1)?,
+            grove_coordinates(
+                values,
+                Day20::DEFAULT.decryption_key,
+                Day20::DEFAULT.mix_rounds,
+            )?,
+        ))
+    }
+
+    fn reference(&self, values: &Vec<isize>) -> Result<Self::Output, Box<dyn error::Error>> {
+        Ok((
+            naive_grove_coordinates(values, 1, 1),
+            naive_grove_coordinates(
+                values,
+                Day20::DEFAULT.decryption_key,
+                Day20::DEFAULT.mix_rounds,
+            ),
+        ))
+    }
+}
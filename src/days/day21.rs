@@ -0,0 +1,309 @@
+use std::{collections::HashMap, error, fmt::Debug, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    checked::{self, Overflow},
+    error::InputError,
+    line_pattern,
+    solution::Solution,
+};
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Name([char; 4]);
+
+impl Debug for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{}{}{}{}",
+            self.0[0], self.0[1], self.0[2], self.0[3]
+        ))
+    }
+}
+
+impl FromStr for Name {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Name, InputError> {
+        match s.chars().collect::<Vec<_>>()[..] {
+            [a, b, c, d] => Ok(Name([a, b, c, d])),
+            _ => Err(InputError::malformed(format!(
+                "monkey names are four letters, not {:?}",
+                s
+            ))),
+        }
+    }
+}
+
+const ROOT: Name = Name(['r', 'o', 'o', 't']);
+const HUMN: Name = Name(['h', 'u', 'm', 'n']);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eql,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Expr {
+    Constant(isize),
+    Binary(BinOp, Name, Name),
+    HumanInput,
+}
+
+line_pattern! {
+    struct ConstantJob = r"(\w+): (\d+)" {
+        name: Name,
+        constant: isize,
+    }
+}
+
+line_pattern! {
+    struct OperationJob = r"(\w+): (\w+) (.) (\w+)" {
+        name: Name,
+        arg1: Name,
+        op: char,
+        arg2: Name,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Monkey {
+    expr: Expr,
+    value: Option<isize>,
+}
+
+/// `a / b`, which unlike the other operations is an error even with checking
+/// off, as dividing by zero would panic in any build.
+fn divide(what: &str, a: isize, b: isize) -> Result<isize, Overflow> {
+    match a.checked_div(b) {
+        Some(_) => checked::div(Day21::DAY, what, a, b),
+        None => Err(Overflow {
+            day: Day21::DAY,
+            operation: format!("{} ({} / {})", what, a, b),
+            division_by_zero: b == 0,
+        }),
+    }
+}
+
+/// Works out what humn has to shout for monkey `name`, whose number isn't
+/// known, to come to `expected`.
+fn solve_for(
+    monkeys: &HashMap<Name, Monkey>,
+    mut name: Name,
+    mut expected: isize,
+) -> Result<isize, Box<dyn error::Error>> {
+    // Each step goes one monkey further from root, so a chain longer than
+    // there are monkeys must be going round in circles
+    for _ in 0..monkeys.len() {
+        let (op, a, b) = match monkeys[&name].expr {
+            Expr::HumanInput => return Ok(expected),
+            Expr::Binary(op, a, b) => (op, a, b),
+            Expr::Constant(_) => {
+                return Err(format!("monkey {:?}'s number was never worked out", name).into())
+            }
+        };
+
+        let (solved, unsolved, is_solved_first) = match (monkeys[&a].value, monkeys[&b].value) {
+            (Some(n), _) => (n, b, true),
+            (None, Some(n)) => (n, a, false),
+            (None, None) => {
+                return Err(
+                    format!("Neither {:?} nor {:?} can be worked out without humn", a, b).into(),
+                )
+            }
+        };
+
+        let what = "working back to humn";
+        expected = match (op, is_solved_first) {
+            (BinOp::Add, _) => checked::sub(Day21::DAY, what, expected, solved)?,
+            (BinOp::Sub, true) => checked::sub(Day21::DAY, what, solved, expected)?,
+            (BinOp::Sub, false) => checked::add(Day21::DAY, what, solved, expected)?,
+            (BinOp::Mul, _) => divide(what, expected, solved)?,
+            (BinOp::Div, true) => divide(what, solved, expected)?,
+            (BinOp::Div, false) => checked::mul(Day21::DAY, what, solved, expected)?,
+            (BinOp::Eql, _) => {
+                return Err("root's equality can't be part of another monkey's job".into())
+            }
+        };
+        name = unsolved;
+    }
+
+    Err("Going round in circles working back to humn".into())
+}
+
+/// Repeatedly fills in the value of every monkey whose arguments are known,
+/// until nothing changes. `Eql` expressions and human input are never solved.
+fn evaluate(monkeys: &mut HashMap<Name, Monkey>) -> Result<(), Overflow> {
+    loop {
+        let updates: HashMap<Name, Monkey> = monkeys
+            .iter()
+            .filter_map(|(name, monkey)| {
+                if monkey.value.is_some() {
+                    return None;
+                }
+
+                match monkey.expr {
+                    Expr::Constant(n) => Some(Ok((
+                        *name,
+                        Monkey {
+                            expr: monkey.expr,
+                            value: Some(n),
+                        },
+                    ))),
+                    Expr::Binary(op, arg1, arg2) => {
+                        match (monkeys[&arg1].value, monkeys[&arg2].value) {
+                            (Some(val1), Some(val2)) => {
+                                let what = format!("monkey {:?}'s number", name);
+                                let value = match op {
+                                    BinOp::Add => checked::add(Day21::DAY, &what, val1, val2),
+                                    BinOp::Sub => checked::sub(Day21::DAY, &what, val1, val2),
+                                    BinOp::Mul => checked::mul(Day21::DAY, &what, val1, val2),
+                                    BinOp::Div => divide(&what, val1, val2),
+                                    _ => return None,
+                                };
+                                Some(value.map(|value| {
+                                    (
+                                        *name,
+                                        Monkey {
+                                            expr: monkey.expr,
+                                            value: Some(value),
+                                        },
+                                    )
+                                }))
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        if updates.is_empty() {
+            return Ok(());
+        } else {
+            monkeys.extend(updates.iter());
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: usize = 21;
+
+    type Parsed = HashMap<Name, Monkey>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let mut monkeys: HashMap<Name, Monkey> = HashMap::new();
+        // Where each monkey was described, for pointing at bad references
+        let mut lines: HashMap<Name, (usize, &str)> = HashMap::new();
+
+        for (idx, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let parse_monkey = || -> Result<(Name, Monkey), InputError> {
+                // Either "name: constant" or "name: arg1 op arg2"
+                if line.split_whitespace().count() == 2 {
+                    let job = ConstantJob::parse_line(line)?;
+                    let monkey = Monkey {
+                        expr: Expr::Constant(job.constant),
+                        value: None,
+                    };
+                    return Ok((job.name, monkey));
+                }
+
+                let job = OperationJob::parse_line(line)?;
+                let op = match job.op {
+                    '+' => BinOp::Add,
+                    '-' => BinOp::Sub,
+                    '*' => BinOp::Mul,
+                    '/' => BinOp::Div,
+                    op => return Err(InputError::unknown_token(op)),
+                };
+                let monkey = Monkey {
+                    expr: Expr::Binary(op, job.arg1, job.arg2),
+                    value: None,
+                };
+                Ok((job.name, monkey))
+            };
+
+            let (name, monkey) = parse_monkey().map_err(|e| e.at(idx + 1, line))?;
+            lines.insert(name, (idx + 1, line));
+            monkeys.insert(name, monkey);
+        }
+
+        for name in &[ROOT, HUMN] {
+            if !monkeys.contains_key(name) {
+                return Err(InputError::invalid_reference(format!(
+                    "no monkey named {:?}",
+                    name
+                )));
+            }
+        }
+        for (name, monkey) in &monkeys {
+            if let Expr::Binary(_, arg1, arg2) = monkey.expr {
+                if let Some(arg) = [arg1, arg2].iter().find(|a| !monkeys.contains_key(a)) {
+                    let (number, line) = lines[name];
+                    return Err(
+                        InputError::invalid_reference(format!("unknown monkey {:?}", arg))
+                            .at(number, line),
+                    );
+                }
+            }
+        }
+
+        Ok(monkeys)
+    }
+
+    fn part1(&self, monkeys: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let mut monkeys = monkeys.clone();
+        evaluate(&mut monkeys)?;
+
+        Ok(monkeys[&ROOT].value.ok_or("Couldn't evaluate root")?)
+    }
+
+    fn part2(&self, monkeys: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let mut monkeys = monkeys.clone();
+        monkeys.insert(
+            HUMN,
+            Monkey {
+                expr: Expr::HumanInput,
+                value: None,
+            },
+        );
+        let (name_a, name_b) = match monkeys[&ROOT].expr {
+            Expr::Binary(_, a, b) => (a, b),
+            _ => return Err("root has no two monkeys to compare".into()),
+        };
+        monkeys.insert(
+            ROOT,
+            Monkey {
+                expr: Expr::Binary(BinOp::Eql, name_a, name_b),
+                value: None,
+            },
+        );
+
+        evaluate(&mut monkeys)?;
+
+        let (solved, unsolved) = match (monkeys[&name_a].value, monkeys[&name_b].value) {
+            (Some(_), Some(_)) => return Err("Neither side of root's equality needs humn".into()),
+            (Some(value), None) => (value, name_b),
+            (None, Some(value)) => (value, name_a),
+            (None, None) => return Err("Neither side of root's equality is known".into()),
+        };
+        let human_input = solve_for(&monkeys, unsolved, solved)?;
+
+        Ok(human_input)
+    }
+}
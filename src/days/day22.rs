@@ -0,0 +1,479 @@
+use std::{convert::TryFrom, error, fmt::Debug, rc::Rc};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::InputError,
+    geometry::{Direction, Point2},
+    grid::Grid,
+    input,
+    solution::Solution,
+    viewer::Simulation,
+};
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Spot {
+    Blank,
+    Floor,
+    Wall,
+    Portal(char),
+    PortalStart(char),
+}
+
+impl TryFrom<char> for Spot {
+    type Error = InputError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            ' ' => Ok(Spot::Blank),
+            '.' => Ok(Spot::Floor),
+            '#' => Ok(Spot::Wall),
+            c => {
+                if c.is_ascii_uppercase() {
+                    Ok(Spot::Portal(c))
+                } else if c.is_ascii_lowercase() {
+                    Ok(Spot::PortalStart(c.to_ascii_uppercase()))
+                } else {
+                    Err(InputError::unknown_token(c))
+                }
+            }
+        }
+    }
+}
+
+impl Debug for Spot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Blank => write!(f, " "),
+            Self::Floor => write!(f, "."),
+            Self::Wall => write!(f, "#"),
+            Self::Portal(c) => write!(f, "{}", c),
+            Self::PortalStart(c) => write!(f, "{}", c.to_ascii_lowercase()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Instruction {
+    Forward(usize),
+    Clockwise,
+    Counterclockwise,
+}
+
+type Point = Point2<isize>;
+
+/// The puzzle's value for each facing in the final password.
+fn facing_score(facing: Direction) -> usize {
+    match facing {
+        Direction::Right => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Up => 3,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct State {
+    pos: Point2<usize>,
+    facing: Direction,
+}
+
+impl State {
+    /// The leftmost open tile of the board's top row, which may be below a
+    /// row of portals. Parsing checks that there is one.
+    fn start(map: &Grid<Spot>) -> Option<Self> {
+        let y = map
+            .rows()
+            .position(|row| row.iter().any(|&s| s == Spot::Floor || s == Spot::Wall))?;
+        let x = map.row(y)?.iter().position(|&s| s == Spot::Floor)?;
+        Some(State {
+            pos: Point2::new(x, y),
+            facing: Direction::Right,
+        })
+    }
+
+    fn apply_instruction(
+        self,
+        i: Instruction,
+        map: &Grid<Spot>,
+        cube: bool,
+    ) -> Result<Self, Box<dyn error::Error>> {
+        match i {
+            Instruction::Forward(n) => self.move_steps(n, self.facing, map, cube),
+            Instruction::Clockwise => Ok(Self {
+                facing: self.facing.turn_cw(),
+                ..self
+            }),
+            Instruction::Counterclockwise => Ok(Self {
+                facing: self.facing.turn_ccw(),
+                ..self
+            }),
+        }
+    }
+
+    fn move_steps(
+        self,
+        steps: usize,
+        facing: Direction,
+        map: &Grid<Spot>,
+        cube: bool,
+    ) -> Result<Self, Box<dyn error::Error>> {
+        let mut result = self;
+
+        for _ in 0..steps {
+            let mut next_facing = result.facing;
+            let mut next_pos = Point::new(result.pos.x as isize, result.pos.y as isize);
+            loop {
+                next_pos += next_facing.offset();
+
+                let tgt_spot = map.get(next_pos.x, next_pos.y);
+
+                let tgt_spot = match tgt_spot {
+                    None | Some(Spot::Blank | Spot::Portal(_) | Spot::PortalStart(_)) if !cube => {
+                        next_pos = flat_wrap(result.pos, next_facing, map);
+                        map.get(next_pos.x, next_pos.y)
+                    }
+                    _ => tgt_spot,
+                };
+
+                match tgt_spot {
+                    None | Some(Spot::Blank) => {
+                        return Err(format!(
+                            "Fell off the edge of the map at {},{}",
+                            next_pos.x, next_pos.y
+                        )
+                        .into())
+                    }
+                    Some(Spot::Floor) => {
+                        result.pos = Point2::new(next_pos.x as usize, next_pos.y as usize);
+                        result.facing = next_facing;
+                        break;
+                    }
+                    Some(Spot::Wall) => return Ok(result),
+                    Some(&spot @ (Spot::Portal(_) | Spot::PortalStart(_))) => {
+                        (next_pos, next_facing) = portal_teleport(next_pos, spot, facing, map)?;
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn to_score(self) -> usize {
+        1000 * (self.pos.y) + 4 * (self.pos.x) + facing_score(self.facing)
+    }
+}
+
+/// Finds the spot on the far side of the board that you wrap around to when
+/// walking off the edge from `pos`, ignoring any portals.
+fn flat_wrap(pos: Point2<usize>, facing: Direction, map: &Grid<Spot>) -> Point {
+    let back: Point = facing.reverse().offset();
+    let mut pos = Point::new(pos.x as isize, pos.y as isize);
+    loop {
+        let behind = pos + back;
+        match map.get(behind.x, behind.y) {
+            Some(Spot::Floor | Spot::Wall) => pos = behind,
+            _ => return pos,
+        }
+    }
+}
+
+fn portal_teleport(
+    next_pos: Point,
+    tgt_spot: Spot,
+    facing: Direction,
+    map: &Grid<Spot>,
+) -> Result<(Point, Direction), Box<dyn error::Error>> {
+    let c = match tgt_spot {
+        Spot::PortalStart(c) => c,
+        Spot::Portal(c) => c,
+        _ => unreachable!("only called on portals"),
+    };
+    let unmatched = || format!("Portal {} has no start or no other end", c);
+
+    // Where each portal starts, and the direction it runs in from there
+    let mut portals: Vec<(Point, Direction)> = Vec::new();
+    for (y, row) in map.rows().enumerate() {
+        let portal_spots: Vec<_> = row
+            .iter()
+            .enumerate()
+            .filter(|(_, &s)| s == Spot::Portal(c) || s == Spot::PortalStart(c))
+            .collect();
+        if portal_spots.len() > 1 {
+            let (x_first, spot_first) = portal_spots.first().unwrap();
+            let (x_last, _) = portal_spots.last().unwrap();
+            if **spot_first == Spot::PortalStart(c) {
+                portals.push((Point::new(*x_first as isize, y as isize), Direction::Right));
+            } else {
+                portals.push((Point::new(*x_last as isize, y as isize), Direction::Left));
+            }
+        }
+    }
+    for (x, column) in map.columns().enumerate() {
+        let portal_spots: Vec<_> = column
+            .enumerate()
+            .filter(|(_, &s)| s == Spot::Portal(c) || s == Spot::PortalStart(c))
+            .collect();
+        if portal_spots.len() > 1 {
+            let (y_first, spot_first) = portal_spots.first().unwrap();
+            let (y_last, _) = portal_spots.last().unwrap();
+            if **spot_first == Spot::PortalStart(c) {
+                portals.push((Point::new(x as isize, *y_first as isize), Direction::Down));
+            } else {
+                portals.push((Point::new(x as isize, *y_last as isize), Direction::Up));
+            }
+        }
+    }
+
+    let portal_offset: usize = match tgt_spot {
+        Spot::PortalStart(_) => 0,
+        Spot::Portal(c) => {
+            if facing.is_horizontal() {
+                let r = map
+                    .column(next_pos.x as usize)
+                    .into_iter()
+                    .flatten()
+                    .enumerate()
+                    .find(|(_, &s)| s == Spot::PortalStart(c))
+                    .ok_or_else(unmatched)?;
+                (next_pos.y - (r.0 as isize)).unsigned_abs()
+            } else {
+                let r = map
+                    .row(next_pos.y as usize)
+                    .into_iter()
+                    .flatten()
+                    .enumerate()
+                    .find(|(_, &s)| s == Spot::PortalStart(c))
+                    .ok_or_else(unmatched)?;
+                (next_pos.x - (r.0 as isize)).unsigned_abs()
+            }
+        }
+        _ => unreachable!("only called on portals"),
+    };
+
+    let (in_portal_idx, _) = portals
+        .iter()
+        .find_position(|(portal_start_pos, portal_dir)| {
+            (portal_start_pos.x == next_pos.x && portal_dir.is_vertical())
+                || (portal_start_pos.y == next_pos.y && portal_dir.is_horizontal())
+        })
+        .ok_or_else(unmatched)?;
+
+    let (_, out_portal) = portals
+        .iter()
+        .enumerate()
+        .find(|(idx, _)| *idx != in_portal_idx)
+        .ok_or_else(unmatched)?;
+
+    let out_portal_pos = out_portal.0 + out_portal.1.offset() * (portal_offset as isize);
+
+    let out_direction = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ]
+    .iter()
+    .find(|dir| {
+        let out_dir_pos = out_portal_pos + dir.offset();
+        let spot = map.get(out_dir_pos.x, out_dir_pos.y);
+        spot == Some(&Spot::Floor) || spot == Some(&Spot::Wall)
+    })
+    .ok_or_else(|| format!("Portal {} doesn't lead onto the board", c))?;
+
+    Ok((out_portal_pos, *out_direction))
+}
+
+/// The board, with its ragged lines filled out with blanks so that every row
+/// is the same width.
+fn parse_map(input: &str) -> Result<Grid<Spot>, InputError> {
+    let map = Grid::parse_padded(input, Spot::Blank, Spot::try_from)?;
+    if map.width() == 0 {
+        return Err(InputError::malformed("empty map"));
+    }
+    if State::start(&map).is_none() {
+        return Err(InputError::malformed(
+            "no open tile to start on in the top row of the map",
+        ));
+    }
+    Ok(map)
+}
+
+fn parse_instructions(input: &str) -> Result<Vec<Instruction>, InputError> {
+    let mut chars = input.chars().peekable();
+    let mut instructions = Vec::new();
+    while chars.peek().is_some() {
+        let maybe_num = chars.peeking_take_while(|c| c.is_ascii_digit()).join("");
+        if !maybe_num.is_empty() {
+            instructions.push(Instruction::Forward(maybe_num.parse()?));
+            continue;
+        }
+
+        instructions.push(match chars.next().unwrap() {
+            'L' => Instruction::Counterclockwise,
+            'R' => Instruction::Clockwise,
+            c => return Err(InputError::unknown_token(c)),
+        });
+    }
+    Ok(instructions)
+}
+
+fn follow_path(
+    map: &Grid<Spot>,
+    instructions: &[Instruction],
+    cube: bool,
+) -> Result<State, Box<dyn error::Error>> {
+    let mut state = State::start(map).ok_or("No open tile to start on")?;
+    for &i in instructions {
+        state = state.apply_instruction(i, map, cube)?;
+    }
+    Ok(state)
+}
+
+/// The path being followed one instruction per step.
+#[derive(Clone)]
+struct Walking {
+    map: Rc<Grid<Spot>>,
+    instructions: Rc<[Instruction]>,
+    cube: bool,
+    state: State,
+    done: usize,
+    /// Every tile walked through so far.
+    trail: Vec<Point2<usize>>,
+    /// Why the walk came to a stop before the end of the path, if it did.
+    error: Option<String>,
+}
+
+impl Walking {
+    fn new(map: &Grid<Spot>, instructions: &[Instruction], cube: bool) -> Option<Self> {
+        Some(Walking {
+            map: Rc::new(map.clone()),
+            instructions: instructions.into(),
+            cube,
+            state: State::start(map)?,
+            done: 0,
+            trail: Vec::new(),
+            error: None,
+        })
+    }
+
+    /// Keeps `result`'s state, or stops the walk on an error.
+    fn advance(&mut self, result: Result<State, Box<dyn error::Error>>) -> bool {
+        match result {
+            Ok(state) => {
+                self.state = state;
+                true
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                false
+            }
+        }
+    }
+}
+
+impl Simulation for Walking {
+    fn step(&mut self) -> bool {
+        if self.error.is_some() {
+            return false;
+        }
+        match self.instructions.get(self.done) {
+            Some(&Instruction::Forward(n)) => {
+                // A tile at a time, to leave a trail
+                for _ in 0..n {
+                    let before = self.state;
+                    let next = before.move_steps(1, before.facing, &self.map, self.cube);
+                    if !self.advance(next) {
+                        return false;
+                    }
+                    if self.state == before {
+                        break;
+                    }
+                    self.trail.push(before.pos);
+                }
+                self.done += 1;
+                true
+            }
+            Some(&i) => {
+                let next = self.state.apply_instruction(i, &self.map, self.cube);
+                if !self.advance(next) {
+                    return false;
+                }
+                self.done += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn status(&self) -> String {
+        let last = match self.done {
+            0 => String::new(),
+            n => format!(" after {:?}", self.instructions[n - 1]),
+        };
+        let status = format!(
+            "instruction {} of {}{}, at {},{} facing {:?}, password {}",
+            self.done,
+            self.instructions.len(),
+            last,
+            self.state.pos.x,
+            self.state.pos.y,
+            self.state.facing,
+            self.state.to_score()
+        );
+        match &self.error {
+            Some(error) => format!("{}; stopped: {}", status, error),
+            None => status,
+        }
+    }
+
+    fn rows(&self) -> Vec<String> {
+        let mut map = self
+            .map
+            .map(|spot| format!("{:?}", spot).chars().next().unwrap());
+        for &pos in &self.trail {
+            map[pos] = '*';
+        }
+        map[self.state.pos] = self.state.facing.to_arrow();
+        map.rows().map(|row| row.iter().collect()).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: usize = 22;
+
+    type Parsed = (Grid<Spot>, Vec<Instruction>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let (map, path) = input::sections(input)?;
+        let map = map.parse(|block| parse_map(&block.text()))?;
+        let instructions = path.parse(|block| match block.lines[..] {
+            [line] => parse_instructions(line).map_err(|e| e.at(1, line)),
+            _ => Err(InputError::malformed("expected the path on one line")),
+        })?;
+
+        Ok((map, instructions))
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let (map, instructions) = parsed;
+        Ok(follow_path(map, instructions, false)?.to_score())
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let (map, instructions) = parsed;
+        Ok(follow_path(map, instructions, true)?.to_score())
+    }
+
+    fn simulation(&self, parsed: &Self::Parsed, part: usize) -> Option<Box<dyn Simulation>> {
+        let (map, instructions) = parsed;
+        Some(Box::new(Walking::new(map, instructions, part == 2)?))
+    }
+}
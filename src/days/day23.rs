@@ -0,0 +1,183 @@
+use std::{collections::HashMap, error};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::InputError,
+    geometry::{Direction8, Point2},
+    grid::SparseGrid,
+    solution::Solution,
+    trace,
+    viewer::Simulation,
+};
+
+use Direction8::*;
+
+/// Each direction an elf might move in, with the three positions it checks
+/// are empty before proposing to.
+const MOVEMENTS_TO_CONSIDER: [(Direction8, [Direction8; 3]); 4] = [
+    (Up, [UpRight, Up, UpLeft]),
+    (Down, [DownRight, Down, DownLeft]),
+    (Left, [UpLeft, Left, DownLeft]),
+    (Right, [UpRight, Right, DownRight]),
+];
+
+/// Marks a position occupied by an elf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf;
+
+type Point = Point2<isize>;
+
+fn consider_move(elf: Point, elves: &SparseGrid<Elf>, round_offset: usize) -> Option<Point> {
+    let occupied = |look_dir: &Direction8| {
+        let p = elf + look_dir.offset();
+        elves.contains(p.x, p.y)
+    };
+
+    if !Direction8::ALL.iter().any(occupied) {
+        return None;
+    }
+
+    for i in 0..MOVEMENTS_TO_CONSIDER.len() {
+        let idx = (i + round_offset) % MOVEMENTS_TO_CONSIDER.len();
+        let (move_dir, looking) = MOVEMENTS_TO_CONSIDER[idx];
+        if !looking.iter().any(occupied) {
+            return Some(elf + move_dir.offset());
+        }
+    }
+    None
+}
+
+/// Runs a single round of elf movement, returning whether any elf proposed a
+/// move.
+fn run_round(elves: &mut SparseGrid<Elf>, round: usize) -> bool {
+    // key is proposed location, value is original elf position
+    let mut proposals: HashMap<Point, Vec<Point>> = HashMap::new();
+    for elf in elves.positions().map(Point::from) {
+        let result = consider_move(elf, elves, round);
+        if let Some(target) = result {
+            proposals.entry(target).or_default().push(elf);
+        }
+    }
+
+    if proposals.is_empty() {
+        return false;
+    }
+
+    for (target, source_elves) in proposals {
+        if let [source] = source_elves[..] {
+            elves.remove(source.x, source.y);
+            elves.insert(target.x, target.y, Elf);
+        }
+    }
+
+    true
+}
+
+/// The elves spreading out one round per step, for `rounds` rounds or until
+/// none of them move.
+#[derive(Clone)]
+struct Spreading {
+    elves: SparseGrid<Elf>,
+    round: usize,
+    rounds: Option<usize>,
+    settled: bool,
+}
+
+impl Simulation for Spreading {
+    fn step(&mut self) -> bool {
+        if self.settled || Some(self.round) == self.rounds {
+            return false;
+        }
+        if !run_round(&mut self.elves, self.round) {
+            self.settled = true;
+            return false;
+        }
+        self.round += 1;
+        true
+    }
+
+    fn status(&self) -> String {
+        let empty = self
+            .elves
+            .bounds()
+            .map_or(0, |bounds| bounds.area() as usize - self.elves.len());
+        format!(
+            "{} rounds, {} empty ground tiles{}",
+            self.round,
+            empty,
+            if self.settled { ", no elf moves" } else { "" }
+        )
+    }
+
+    fn rows(&self) -> Vec<String> {
+        self.elves
+            .render(|elf| if elf.is_some() { '#' } else { '.' })
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    fn origin(&self) -> (isize, isize) {
+        self.elves
+            .bounds()
+            .map_or((0, 0), |bounds| (bounds.min.x, bounds.min.y))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: usize = 23;
+
+    type Parsed = SparseGrid<Elf>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let elves = SparseGrid::parse(input, |c| match c {
+            '#' => Ok(Some(Elf)),
+            '.' => Ok(None),
+            _ => Err(InputError::unknown_token(c)),
+        })?;
+
+        if elves.is_empty() {
+            return Err(InputError::malformed("no elves found"));
+        }
+
+        Ok(elves)
+    }
+
+    fn part1(&self, elves: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let mut elves = elves.clone();
+        for round in 0..10 {
+            run_round(&mut elves, round);
+        }
+
+        let area = elves.bounds().ok_or("No elves left")?.area() as usize;
+        Ok(area - elves.len())
+    }
+
+    fn part2(&self, elves: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let mut elves = elves.clone();
+        let mut round = 0;
+        while run_round(&mut elves, round) {
+            round += 1;
+            if round % 100 == 0 {
+                trace!("Day 23: {} rounds and the elves are still moving", round);
+            }
+        }
+
+        Ok(round + 1)
+    }
+
+    fn simulation(&self, elves: &Self::Parsed, part: usize) -> Option<Box<dyn Simulation>> {
+        Some(Box::new(Spreading {
+            elves: elves.clone(),
+            round: 0,
+            rounds: if part == 1 { Some(10) } else { None },
+            settled: false,
+        }))
+    }
+}
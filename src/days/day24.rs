@@ -0,0 +1,192 @@
+use std::{convert::TryInto, error, iter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::InputError,
+    geometry::{Direction, Point2},
+    grid::Grid,
+    pathfinding::bfs,
+    solution::Solution,
+};
+
+type Point = Point2<i32>;
+
+/// Wraps a point back into a `width` by `height` area, as blizzards do when
+/// they reach a wall.
+fn wrap(point: Point, width: i32, height: i32) -> Point {
+    Point::new(point.x.rem_euclid(width), point.y.rem_euclid(height))
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct FindPathResult {
+    steps: usize,
+    x_phase: u8,
+    y_phase: u8,
+}
+
+/// The valley floor inside the walls. The blizzard grids are indexed by phase:
+/// vertical blizzards repeat every `max_y + 1` minutes and horizontal ones
+/// every `max_x + 1`.
+pub struct Valley {
+    vert_blizzards: Vec<Grid<bool>>,
+    horiz_blizzards: Vec<Grid<bool>>,
+    max_x: u8,
+    max_y: u8,
+}
+
+impl Valley {
+    fn start_point(&self) -> Point {
+        Point::new(0, -1)
+    }
+
+    fn end_point(&self) -> Point {
+        Point::new(self.max_x as i32, (self.max_y + 1) as i32)
+    }
+}
+
+fn find_path(
+    start: (u8, u8, Point),
+    end: Point,
+    valley: &Valley,
+) -> Result<FindPathResult, Box<dyn error::Error>> {
+    let (max_x, max_y) = (valley.max_x, valley.max_y);
+    let waiting = Point::default();
+
+    let path = bfs(
+        Some(start),
+        |&(x_phase, y_phase, point)| {
+            let next_x_phase = (x_phase + 1) % (max_x + 1);
+            let next_y_phase = (y_phase + 1) % (max_y + 1);
+
+            Direction::ALL
+                .iter()
+                .map(|dir| dir.offset())
+                .chain(iter::once(waiting))
+                .filter(move |&step| {
+                    let tgt = point + step;
+                    if tgt == end {
+                        return true;
+                    }
+
+                    let (x, y) = (tgt.x as isize, tgt.y as isize);
+                    match (
+                        valley.vert_blizzards[next_y_phase as usize].get(x, y),
+                        valley.horiz_blizzards[next_x_phase as usize].get(x, y),
+                    ) {
+                        (Some(&vert), Some(&horiz)) => !(vert || horiz),
+                        // Allow waiting in the start position, even though it's
+                        // outside the valley
+                        _ => point == start.2 && step == waiting,
+                    }
+                })
+                .map(move |step| (next_x_phase, next_y_phase, point + step))
+        },
+        |&(_, _, point)| point == end,
+    )
+    .ok_or("Unable to find exit")?;
+
+    let &(x_phase, y_phase, _) = path.goal();
+    Ok(FindPathResult {
+        steps: path.cost,
+        x_phase,
+        y_phase,
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: usize = 24;
+
+    type Parsed = Valley;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let lines: Vec<&str> = input
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+        if lines.len() < 3 {
+            return Err(InputError::malformed("valley is too small"));
+        }
+
+        // Everything between the walls
+        let interior = lines[1..lines.len() - 1]
+            .iter()
+            .map(|line| line.get(1..line.len() - 1).unwrap_or(""))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let raw_blizzards = Grid::parse(&interior, |c| match c {
+            '.' => Ok(None),
+            c => Direction::from_char(c)
+                .map(Some)
+                .ok_or_else(|| InputError::unknown_token(c)),
+        })
+        .map_err(|e| e.shifted(1))?;
+
+        if raw_blizzards.width() == 0 || raw_blizzards.height() == 0 {
+            return Err(InputError::malformed("valley is too small"));
+        }
+        // The blizzards' phases go up to the width and height, so those have
+        // to fit in a u8 too, not just the largest coordinates
+        let width: u8 = raw_blizzards
+            .width()
+            .try_into()
+            .map_err(|_| InputError::malformed("valley is too wide"))?;
+        let height: u8 = raw_blizzards
+            .height()
+            .try_into()
+            .map_err(|_| InputError::malformed("valley is too tall"))?;
+        let (max_x, max_y) = (width - 1, height - 1);
+
+        let blizzards_by_phase = |phases: u8, moves: fn(Direction) -> bool| {
+            (0..phases)
+                .map(|phase| {
+                    let mut spots = raw_blizzards.map(|_| false);
+                    for ((x, y), blizzard_dir) in raw_blizzards.iter() {
+                        if let Some(dir) = blizzard_dir.filter(|&dir| moves(dir)) {
+                            let point = wrap(
+                                Point::new(x as i32, y as i32) + dir.offset() * phase as i32,
+                                max_x as i32 + 1,
+                                max_y as i32 + 1,
+                            );
+                            spots[(point.x as usize, point.y as usize)] = true;
+                        }
+                    }
+                    spots
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let vert_blizzards = blizzards_by_phase(height, Direction::is_vertical);
+        let horiz_blizzards = blizzards_by_phase(width, Direction::is_horizontal);
+
+        Ok(Valley {
+            vert_blizzards,
+            horiz_blizzards,
+            max_x,
+            max_y,
+        })
+    }
+
+    fn part1(&self, valley: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let s1 = find_path((0, 0, valley.start_point()), valley.end_point(), valley)?;
+
+        Ok(s1.steps)
+    }
+
+    fn part2(&self, valley: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let start_point = valley.start_point();
+        let end_point = valley.end_point();
+
+        let s1 = find_path((0, 0, start_point), end_point, valley)?;
+        let s2 = find_path((s1.x_phase, s1.y_phase, end_point), start_point, valley)?;
+        let s3 = find_path((s2.x_phase, s2.y_phase, start_point), end_point, valley)?;
+
+        Ok(s1.steps + s2.steps + s3.steps)
+    }
+}
@@ -0,0 +1,93 @@
+use std::{convert::TryInto, error};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{checked, error::InputError, input::parse_lines, solution::Solution};
+
+fn parse_snafu_digit(input: char) -> Option<i64> {
+    match input {
+        '=' => Some(-2),
+        '-' => Some(-1),
+        '0' => Some(0),
+        '1' => Some(1),
+        '2' => Some(2),
+        _ => None,
+    }
+}
+
+/// Reads a SNAFU number. Its arithmetic is always checked, whether or not
+/// `--checked` is on, as a line too long for an `i64` is malformed input.
+fn parse_snafu(input: &str) -> Result<i64, InputError> {
+    let too_long = || InputError::malformed("number too long");
+    input
+        .chars()
+        .rev()
+        .enumerate()
+        .try_fold(0i64, |total, (pos, c)| {
+            let digit_value = parse_snafu_digit(c).ok_or_else(|| InputError::unknown_token(c))?;
+            let place: u32 = pos.try_into().map_err(|_| too_long())?;
+            5i64.checked_pow(place)
+                .and_then(|place_value| digit_value.checked_mul(place_value))
+                .and_then(|value| total.checked_add(value))
+                .ok_or_else(too_long)
+        })
+}
+
+/// The SNAFU digit for a number that leaves `remainder` when divided by 5,
+/// which has to be in `0..5`.
+fn encode_snafu_digit(remainder: i64) -> char {
+    match remainder {
+        0 => '0',
+        1 => '1',
+        2 => '2',
+        3 => '=',
+        4 => '-',
+        _ => unreachable!("{} isn't a remainder of 5", remainder),
+    }
+}
+
+fn encode_snafu(input: i64) -> String {
+    if input == 0 {
+        return "0".into();
+    }
+
+    let mut digits = Vec::new();
+    let mut rest = input;
+    while rest != 0 {
+        let remainder = rest.rem_euclid(5);
+        digits.push(encode_snafu_digit(remainder));
+        // 3 and 4 are written as -2 and -1, carrying one into the next place
+        rest = rest.div_euclid(5) + if remainder > 2 { 1 } else { 0 };
+    }
+    digits.iter().rev().collect()
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: usize = 25;
+
+    type Parsed = Vec<i64>;
+    type Part1 = String;
+    type Part2 = &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        parse_lines(input, parse_snafu)
+    }
+
+    fn part1(&self, numbers: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let total = checked::sum(
+            Day25::DAY,
+            "sum of fuel requirements",
+            numbers.iter().copied(),
+        )?;
+        Ok(encode_snafu(total))
+    }
+
+    fn part2(&self, _numbers: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        // There's no second puzzle on the last day, just a star for finishing
+        // all the others.
+        Ok("Merry Christmas!")
+    }
+}
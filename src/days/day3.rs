@@ -0,0 +1,77 @@
+use std::{collections::HashSet, convert::TryInto, error};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::InputError, input::parse_lines, solution::Solution};
+
+fn priority(c: char) -> Result<usize, Box<dyn error::Error>> {
+    let mut n: u32 = c.into();
+    if n >= 97 {
+        n -= 96;
+    } else {
+        n -= 64 - 26;
+    }
+    Ok(n.try_into()?)
+}
+
+fn priorities(s: &str) -> Result<HashSet<usize>, Box<dyn error::Error>> {
+    s.chars().map(priority).collect()
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: usize = 3;
+
+    /// One rucksack per line.
+    type Parsed = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        parse_lines(input, |line| {
+            match line.chars().find(|c| !c.is_ascii_alphabetic()) {
+                None => Ok(line.to_owned()),
+                Some(c) => Err(InputError::unknown_token(c)),
+            }
+        })
+    }
+
+    fn part1(&self, rucksacks: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        rucksacks
+            .iter()
+            .map(|line| {
+                let (half_a, half_b) = line.split_at(line.len() / 2);
+                let (pri_a, pri_b) = (priorities(half_a)?, priorities(half_b)?);
+                let intersect: HashSet<usize> = pri_a.intersection(&pri_b).copied().collect();
+                intersect
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| "Empty intersection".into())
+            })
+            .sum()
+    }
+
+    fn part2(&self, rucksacks: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        rucksacks
+            .chunks(3)
+            .map(|chunk| {
+                if chunk.len() < 3 {
+                    return Ok(0);
+                }
+
+                let pri1 = priorities(&chunk[0])?;
+                let pri2 = priorities(&chunk[1])?;
+                let pri3 = priorities(&chunk[2])?;
+
+                let intersect: HashSet<usize> = pri1.intersection(&pri2).copied().collect();
+                let intersect: HashSet<usize> = intersect.intersection(&pri3).copied().collect();
+                intersect
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| "Empty intersection".into())
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::error;
+
+use crate::{error::InputError, input::parse_lines, line_pattern, solution::Solution};
+
+line_pattern! {
+    /// The section assignments for one pair of elves.
+    pub struct Pair = r"(\d+)-(\d+),(\d+)-(\d+)" {
+        start_a: usize,
+        end_a: usize,
+        start_b: usize,
+        end_b: usize,
+    }
+}
+
+impl Pair {
+    /// Whether either elf's sections include all of the other's.
+    fn fully_contained(&self) -> bool {
+        (self.start_a <= self.start_b && self.end_a >= self.end_b)
+            || (self.start_b <= self.start_a && self.end_b >= self.end_a)
+    }
+
+    fn overlapping(&self) -> bool {
+        (self.start_a >= self.start_b && self.start_a <= self.end_b)
+            || (self.start_b >= self.start_a && self.start_b <= self.end_a)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: usize = 4;
+
+    type Parsed = Vec<Pair>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        parse_lines(input, Pair::parse_line)
+    }
+
+    fn part1(&self, pairs: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(pairs.iter().filter(|pair| pair.fully_contained()).count())
+    }
+
+    fn part2(&self, pairs: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        Ok(pairs.iter().filter(|pair| pair.overlapping()).count())
+    }
+}
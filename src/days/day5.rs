@@ -0,0 +1,120 @@
+use std::{collections::VecDeque, error, iter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::InputError, line_pattern, solution::Solution};
+
+line_pattern! {
+    pub struct Move = r"move (\d+) from (\d+) to (\d+)" {
+        quantity: usize,
+        src: usize,
+        dst: usize,
+    }
+}
+
+pub struct Crates {
+    columns: Vec<VecDeque<char>>,
+    moves: Vec<Move>,
+}
+
+impl Crates {
+    fn rearrange(&self, one_at_a_time: bool) -> Result<String, Box<dyn error::Error>> {
+        let mut columns = self.columns.clone();
+
+        for m in &self.moves {
+            let mut stack = VecDeque::<char>::new();
+            for _ in 0..m.quantity {
+                let item = columns
+                    .get_mut(m.src.wrapping_sub(1))
+                    .ok_or("Bad source column index")?
+                    .pop_back()
+                    .ok_or("Empty source column")?;
+                if one_at_a_time {
+                    stack.push_back(item);
+                } else {
+                    stack.push_front(item);
+                }
+            }
+            columns
+                .get_mut(m.dst.wrapping_sub(1))
+                .ok_or("Bad dest column")?
+                .append(&mut stack);
+        }
+
+        let mut tops = String::new();
+        for column in columns {
+            tops.push(*column.back().ok_or("Empty column")?);
+        }
+        Ok(tops)
+    }
+}
+
+/// Handles one line of the drawing or procedure, adding any crates it shows
+/// to `columns` and returning the move it describes, if it's a move.
+fn parse_line(line: &str, columns: &mut [VecDeque<char>]) -> Result<Option<Move>, InputError> {
+    if line.contains('[') {
+        for (idx, item) in line.chars().skip(1).step_by(4).enumerate() {
+            if item == ' ' {
+                continue;
+            }
+            columns
+                .get_mut(idx)
+                .ok_or_else(|| InputError::malformed("crate outside of any column"))?
+                .push_front(item);
+        }
+        return Ok(None);
+    }
+
+    // The column numbers under the drawing and the blank line after them
+    if !line.starts_with("move") {
+        return Ok(None);
+    }
+    let m = Move::parse_line(line.trim_end())?;
+    if let Some(n) = [m.src, m.dst]
+        .iter()
+        .find(|&&n| n == 0 || n > columns.len())
+    {
+        return Err(InputError::invalid_reference(format!("no column {}", n)));
+    }
+    Ok(Some(m))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: usize = 5;
+
+    type Parsed = Crates;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let lines: Vec<&str> = input.lines().collect();
+
+        let first_line = lines
+            .first()
+            .ok_or_else(|| InputError::malformed("empty input"))?;
+        let num_columns = (first_line.len() + 1) / 4;
+        let mut columns: Vec<VecDeque<char>> = iter::repeat_with(VecDeque::<char>::new)
+            .take(num_columns)
+            .collect();
+        let mut moves: Vec<Move> = Vec::new();
+
+        for (idx, line) in lines.into_iter().enumerate() {
+            if let Some(m) = parse_line(line, &mut columns).map_err(|e| e.at(idx + 1, line))? {
+                moves.push(m);
+            }
+        }
+
+        Ok(Crates { columns, moves })
+    }
+
+    fn part1(&self, crates: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        crates.rearrange(true)
+    }
+
+    fn part2(&self, crates: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        crates.rearrange(false)
+    }
+}
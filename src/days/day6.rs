@@ -0,0 +1,53 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    error,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::InputError, solution::Solution};
+
+const START_OF_PACKET_LEN: usize = 4;
+const START_OF_MESSAGE_LEN: usize = 14;
+
+fn find_marker(s: &str, marker_len: usize) -> Result<usize, Box<dyn error::Error>> {
+    let mut buf = VecDeque::<char>::new();
+    for (idx, c) in s.chars().enumerate() {
+        buf.push_back(c);
+        if buf.len() > marker_len {
+            buf.pop_front();
+        }
+        if buf.len() == marker_len {
+            let set: HashSet<char> = buf.iter().copied().collect();
+            if set.len() == marker_len {
+                return Ok(idx + 1);
+            }
+        }
+    }
+
+    Err(format!("No marker of length {} found", marker_len).into())
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: usize = 6;
+
+    /// The datastream buffer.
+    type Parsed = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part1(&self, s: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        find_marker(s, START_OF_PACKET_LEN)
+    }
+
+    fn part2(&self, s: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        find_marker(s, START_OF_MESSAGE_LEN)
+    }
+}
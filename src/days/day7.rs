@@ -0,0 +1,169 @@
+use std::error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{diagnostics, error::InputError, solution::Solution};
+
+#[derive(Debug)]
+struct File {
+    size: u64,
+}
+
+#[derive(Debug)]
+struct Dir {
+    name: String,
+    subdirs: Vec<Dir>,
+    files: Vec<File>,
+}
+
+#[derive(Debug)]
+pub struct DirReport {
+    name: String,
+    depth: usize,
+    size: u64,
+}
+
+/// The sum of `sizes`, or `None` if it doesn't fit in a u64.
+fn total_size(sizes: impl IntoIterator<Item = u64>) -> Option<u64> {
+    sizes
+        .into_iter()
+        .try_fold(0u64, |total, size| total.checked_add(size))
+}
+
+fn dir_reports(dir: &Dir) -> Result<Vec<DirReport>, InputError> {
+    let mut reports: Vec<DirReport> = Vec::new();
+    for subdir in &dir.subdirs {
+        reports.extend(dir_reports(subdir)?.into_iter().map(|r| DirReport {
+            name: (dir.name.clone() + "/" + &r.name),
+            depth: r.depth + 1,
+            size: r.size,
+        }));
+    }
+    let sub_sizes = reports.iter().filter(|d| d.depth == 1).map(|d| d.size);
+    let local_sizes = dir.files.iter().map(|f| f.size);
+    let size = total_size(sub_sizes.chain(local_sizes)).ok_or_else(|| {
+        let name = if dir.name.is_empty() { "/" } else { &dir.name };
+        InputError::malformed(format!("size of directory {} overflows", name))
+    })?;
+    reports.push(DirReport {
+        name: dir.name.clone(),
+        depth: 0,
+        size,
+    });
+    Ok(reports)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day7 {
+    pub disk_size: u64,
+    /// Free space the update needs.
+    pub space_needed: u64,
+    /// The largest a directory can be and still count towards part 1.
+    pub small_dir_size: u64,
+}
+
+impl Day7 {
+    pub const DEFAULT: Day7 = Day7 {
+        disk_size: 70000000,
+        space_needed: 30000000,
+        small_dir_size: 100000,
+    };
+}
+
+impl Solution for Day7 {
+    const DAY: usize = 7;
+
+    /// Every directory with its total size; the root directory comes last.
+    type Parsed = Vec<DirReport>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let mut root = Dir {
+            name: "".into(),
+            subdirs: Vec::new(),
+            files: Vec::new(),
+        };
+
+        let mut cur_path: Vec<String> = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(cmd) = line.strip_prefix("$ ") {
+                if let Some(tgt) = cmd.strip_prefix("cd ") {
+                    if tgt == "/" {
+                        cur_path = Vec::new();
+                    } else if tgt == ".." {
+                        cur_path.pop();
+                    } else {
+                        cur_path.push(tgt.into());
+                    }
+                }
+            } else {
+                let mut cur_dir: &mut Dir = &mut root;
+                for entry in &cur_path {
+                    cur_dir = cur_dir
+                        .subdirs
+                        .iter_mut()
+                        .find(|subdir| &subdir.name == entry)
+                        .ok_or_else(|| {
+                            InputError::invalid_reference(format!(
+                                "cd into unknown directory {}",
+                                entry
+                            ))
+                            .at(idx + 1, line)
+                        })?;
+                }
+
+                let (size, name) = line.split_once(' ').ok_or_else(|| {
+                    InputError::malformed("expected a size and a name").at(idx + 1, line)
+                })?;
+                if size == "dir" {
+                    cur_dir.subdirs.push(Dir {
+                        name: name.into(),
+                        subdirs: Vec::new(),
+                        files: Vec::new(),
+                    })
+                } else {
+                    let size: u64 = size
+                        .parse()
+                        .map_err(|e| InputError::from(e).at(idx + 1, line))?;
+                    cur_dir.files.push(File { size });
+                }
+            }
+        }
+
+        let reports = dir_reports(&root)?;
+        diagnostics::note("directories", reports.len());
+
+        Ok(reports)
+    }
+
+    fn part1(&self, reports: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let small_sizes = reports
+            .iter()
+            .map(|r| r.size)
+            .filter(|&size| size <= self.small_dir_size);
+        Ok(total_size(small_sizes).ok_or("Total size of the small directories overflows")?)
+    }
+
+    fn part2(&self, reports: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let used = reports.last().ok_or("No root directory")?.size;
+        let free_space = self
+            .disk_size
+            .checked_sub(used)
+            .ok_or("Disk is over capacity")?;
+        let needed = self.space_needed.saturating_sub(free_space);
+        diagnostics::note("free_space", free_space);
+        diagnostics::note("needed", needed);
+
+        let delete = reports
+            .iter()
+            .filter(|r| r.size >= needed)
+            .min_by_key(|r| r.size);
+
+        Ok(delete.ok_or("No directory to delete")?.size)
+    }
+}
@@ -0,0 +1,152 @@
+use std::error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::InputError,
+    export::{blend, Image, Rgb},
+    grid::Grid,
+    solution::Solution,
+};
+
+#[derive(Debug, Clone)]
+pub struct Tree {
+    height: i32,
+    visible: bool,
+}
+
+fn apply_visible<'a, T>(iter: T)
+where
+    T: Iterator<Item = &'a mut Tree>,
+{
+    let mut height: i32 = -1;
+    for tree in iter {
+        if tree.height > height {
+            tree.visible = true;
+            height = tree.height;
+        }
+    }
+}
+
+/// The forest with every tree that can be seen from outside it marked.
+fn mark_visible(forest: &Grid<Tree>) -> Grid<Tree> {
+    let mut forest = forest.clone();
+
+    for row_num in 0..forest.height() {
+        if let Some(row) = forest.row_mut(row_num) {
+            apply_visible(row.iter_mut());
+            apply_visible(row.iter_mut().rev());
+        }
+    }
+
+    for col_num in 0..forest.width() {
+        if let Some(column) = forest.column_mut(col_num) {
+            apply_visible(column);
+        }
+        if let Some(column) = forest.column_mut(col_num) {
+            apply_visible(column.rev());
+        }
+    }
+
+    forest
+}
+
+fn scenic_score(forest: &Grid<Tree>, col_num: usize, row_num: usize) -> usize {
+    let offsets: Vec<(isize, isize)> = vec![(-1, 0), (1, 0), (0, -1), (0, 1)];
+    let start_height = forest[(col_num, row_num)].height;
+    let mut score: usize = 1;
+    for offset in offsets.iter() {
+        let mut offset_score: usize = 0;
+        let mut x = col_num as isize;
+        let mut y = row_num as isize;
+        loop {
+            x += offset.0;
+            y += offset.1;
+            match forest.get(x, y) {
+                Some(other) => {
+                    offset_score += 1;
+                    if other.height >= start_height {
+                        break;
+                    }
+                }
+                None => break,
+            }
+        }
+        score *= offset_score;
+    }
+    score
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: usize = 8;
+
+    type Parsed = Grid<Tree>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        let forest = Grid::parse(input, |c| {
+            Ok(Tree {
+                height: c.to_digit(10).ok_or_else(|| InputError::unknown_token(c))? as i32,
+                visible: false,
+            })
+        })?;
+
+        if forest.width() == 0 {
+            return Err(InputError::malformed("empty forest"));
+        }
+
+        Ok(forest)
+    }
+
+    fn part1(&self, forest: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        let forest = mark_visible(forest);
+        let visible_count: usize = forest.iter().filter(|(_, tree)| tree.visible).count();
+        Ok(visible_count)
+    }
+
+    fn part2(&self, forest: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        let best_score = forest
+            .positions()
+            .map(|(col_num, row_num)| scenic_score(forest, col_num, row_num))
+            .max()
+            .unwrap_or(0);
+
+        Ok(best_score)
+    }
+
+    /// Part 1 shows the trees' heights, in green for the ones that can be
+    /// seen from outside and grey for the rest. Part 2 shows how scenic each
+    /// tree is, with the best one in red.
+    fn pictures(&self, forest: &Self::Parsed, part: usize) -> Option<Vec<Image>> {
+        let mut image = Image::new(forest.width(), forest.height(), Rgb::WHITE);
+        if part == 1 {
+            for ((x, y), tree) in mark_visible(forest).iter() {
+                let (short, tall) = if tree.visible {
+                    (Rgb(200, 240, 160), Rgb(20, 100, 20))
+                } else {
+                    (Rgb(220, 220, 220), Rgb(60, 60, 60))
+                };
+                image.set(x, y, blend(short, tall, tree.height as usize, 9));
+            }
+        } else {
+            let scores: Vec<((usize, usize), usize)> = forest
+                .positions()
+                .map(|(x, y)| ((x, y), scenic_score(forest, x, y)))
+                .collect();
+            let best = scores.iter().map(|&(_, score)| score).max().unwrap_or(0);
+            for &((x, y), score) in &scores {
+                let color = if score == best {
+                    Rgb(208, 32, 32)
+                } else {
+                    blend(Rgb::BLACK, Rgb(250, 220, 80), score, best)
+                };
+                image.set(x, y, color);
+            }
+        }
+        Some(vec![image])
+    }
+}
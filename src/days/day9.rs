@@ -0,0 +1,179 @@
+use std::{collections::HashSet, error, iter, rc::Rc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::InputError,
+    geometry::{BoundingBox2, Direction, Point2},
+    input::parse_lines,
+    solution::Solution,
+    viewer::Simulation,
+};
+
+type Point = Point2<i32>;
+
+/// Pulls a knot back alongside the one ahead of it, moving at most one step
+/// along each axis as the puzzle describes.
+fn move_next_to(knot: &mut Point, other: Point) {
+    if knot.chebyshev(other) > 1 {
+        *knot += (other - *knot).signum();
+    }
+}
+
+/// A head movement: the direction and the number of steps.
+type Motion = (Direction, usize);
+
+/// A rope being dragged around by its head, one step of a motion at a time.
+#[derive(Clone)]
+struct Rope {
+    motions: Rc<[Motion]>,
+    /// The motion under way, and how many of its steps have been taken.
+    motion: usize,
+    moved: usize,
+    knots: Vec<Point>,
+    tail_positions: HashSet<Point>,
+}
+
+impl Rope {
+    fn new(motions: &[Motion], rope_len: usize) -> Self {
+        let knots: Vec<Point> = iter::repeat_n(Point::default(), rope_len).collect();
+        Rope {
+            motions: motions.into(),
+            motion: 0,
+            moved: 0,
+            tail_positions: knots.last().copied().into_iter().collect(),
+            knots,
+        }
+    }
+
+    /// Everywhere the rope is or has been, along with the start.
+    fn bounds(&self) -> BoundingBox2<i32> {
+        BoundingBox2::from_points(
+            self.tail_positions
+                .iter()
+                .chain(&self.knots)
+                .copied()
+                .chain(iter::once(Point::default())),
+        )
+        .unwrap()
+    }
+}
+
+impl Simulation for Rope {
+    fn step(&mut self) -> bool {
+        while self.motion < self.motions.len() && self.moved == self.motions[self.motion].1 {
+            self.motion += 1;
+            self.moved = 0;
+        }
+        let direction = match self.motions.get(self.motion) {
+            Some(&(direction, _)) => direction,
+            None => return false,
+        };
+
+        self.knots[0] += direction.offset();
+        for idx in 1..self.knots.len() {
+            let upstream = self.knots[idx - 1];
+            move_next_to(&mut self.knots[idx], upstream);
+        }
+        self.tail_positions.insert(*self.knots.last().unwrap());
+        self.moved += 1;
+        true
+    }
+
+    fn status(&self) -> String {
+        let motion = match self.motions.get(self.motion) {
+            Some((direction, steps)) => format!(" ({:?} {})", direction, steps),
+            None => String::new(),
+        };
+        format!(
+            "motion {} of {}{}, {} positions visited by the tail",
+            (self.motion + 1).min(self.motions.len()),
+            self.motions.len(),
+            motion,
+            self.tail_positions.len()
+        )
+    }
+
+    /// Drawn like the puzzle does: the head as `H`, the other knots by
+    /// number (or `T` for a two-knot rope), and where the tail has been as
+    /// `#`.
+    fn rows(&self) -> Vec<String> {
+        let bounds = self.bounds();
+        let mut rows: Vec<Vec<char>> = (0..bounds.height())
+            .map(|_| vec!['.'; bounds.width() as usize])
+            .collect();
+        let mut mark = |p: Point, c: char| {
+            rows[(p.y - bounds.min.y) as usize][(p.x - bounds.min.x) as usize] = c;
+        };
+
+        for &p in &self.tail_positions {
+            mark(p, '#');
+        }
+        mark(Point::default(), 's');
+        for (idx, &knot) in self.knots.iter().enumerate().rev() {
+            let c = match idx {
+                0 => 'H',
+                _ if self.knots.len() == 2 => 'T',
+                _ => std::char::from_digit(idx as u32, 36).unwrap(),
+            };
+            mark(knot, c);
+        }
+        rows.into_iter()
+            .map(|row| row.into_iter().collect())
+            .collect()
+    }
+
+    fn origin(&self) -> (isize, isize) {
+        let bounds = self.bounds();
+        (bounds.min.x as isize, bounds.min.y as isize)
+    }
+}
+
+fn tail_positions(motions: &[Motion], rope_len: usize) -> usize {
+    let mut rope = Rope::new(motions, rope_len);
+    while rope.step() {}
+    rope.tail_positions.len()
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: usize = 9;
+
+    type Parsed = Vec<Motion>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError> {
+        parse_lines(input, |line| {
+            let mut items = line.split_ascii_whitespace();
+            let direction = items.next().unwrap();
+            let steps: usize = items
+                .next()
+                .ok_or_else(|| InputError::malformed("no step count"))?
+                .parse()?;
+
+            let direction = direction
+                .parse()
+                .ok()
+                .and_then(Direction::from_char)
+                .ok_or_else(|| InputError::unknown_token(direction))?;
+
+            Ok((direction, steps))
+        })
+    }
+
+    fn part1(&self, motions: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(tail_positions(motions, 2))
+    }
+
+    fn part2(&self, motions: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        Ok(tail_positions(motions, 10))
+    }
+
+    fn simulation(&self, motions: &Self::Parsed, part: usize) -> Option<Box<dyn Simulation>> {
+        let rope_len = if part == 1 { 2 } else { 10 };
+        Some(Box::new(Rope::new(motions, rope_len)))
+    }
+}
@@ -0,0 +1,80 @@
+use std::error;
+
+use crate::{differential::AnyDifferential, params::Params, solution::AnySolution};
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// Every day's solution, in order. `ALL[n - 1]` is day `n`.
+pub const ALL: [&dyn AnySolution; 25] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7::DEFAULT,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15::REAL,
+    &day16::Day16::DEFAULT,
+    &day17::Day17::DEFAULT,
+    &day18::Day18,
+    &day19::Day19::DEFAULT,
+    &day20::Day20::DEFAULT,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+/// Every solver that has a brute-force reference to check it against.
+pub const DIFFERENTIAL: [&dyn AnyDifferential; 4] = [
+    &day15::Reference,
+    &day17::Reference,
+    &day19::Reference,
+    &day20::Reference,
+];
+
+pub fn get(day: usize) -> Option<&'static dyn AnySolution> {
+    day.checked_sub(1).and_then(|idx| ALL.get(idx)).copied()
+}
+
+/// Day `day`'s solver with `params` in place of its defaults.
+pub fn configured(
+    day: usize,
+    params: &Params,
+) -> Result<Box<dyn AnySolution>, Box<dyn error::Error>> {
+    get(day)
+        .ok_or_else(|| format!("No solver for day {}", day))?
+        .configured(params)
+}
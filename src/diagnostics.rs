@@ -0,0 +1,35 @@
+use std::cell::RefCell;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Named values a day reports alongside its answer, sorted by name.
+pub type Diagnostics = Map<String, Value>;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Diagnostics>> = const { RefCell::new(None) };
+}
+
+/// Records `value` under `name` for whoever is collecting, replacing anything
+/// noted under that name before. Does nothing outside `collect`, and so costs
+/// nothing when the answers are all that's wanted.
+///
+/// Only notes made on the collecting thread are kept, so call this outside of
+/// any parallel iterators.
+pub fn note(name: &str, value: impl Serialize) {
+    CURRENT.with(|current| {
+        if let Some(diagnostics) = current.borrow_mut().as_mut() {
+            let value = serde_json::to_value(value)
+                .unwrap_or_else(|e| Value::String(format!("<unserializable: {}>", e)));
+            diagnostics.insert(name.to_string(), value);
+        }
+    });
+}
+
+/// Runs `f`, returning its result along with everything it noted.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Diagnostics) {
+    let outer = CURRENT.with(|current| current.replace(Some(Diagnostics::new())));
+    let result = f();
+    let diagnostics = CURRENT.with(|current| current.replace(outer));
+    (result, diagnostics.unwrap_or_default())
+}
@@ -0,0 +1,229 @@
+use std::{error, fmt::Debug, panic};
+
+use serde::Serialize;
+
+use crate::{verbose, verify::panic_message};
+
+/// A small, fast, seedable random number generator (SplitMix64). Good enough
+/// for making up puzzle inputs, and the same seed always gives the same
+/// inputs.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `min` to `max`, both inclusive.
+    pub fn range(&mut self, min: isize, max: isize) -> isize {
+        assert!(min <= max, "empty range {}..={}", min, max);
+        let span = (max - min) as u64 + 1;
+        min + (self.next_u64() % span) as isize
+    }
+
+    /// A number from 0 up to but not including `len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "can't pick from nothing");
+        (self.next_u64() % len as u64) as usize
+    }
+
+    /// True with probability `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.next_u64() % denominator < numerator
+    }
+}
+
+/// An optimized solver paired with a slow but obviously correct one, and a way
+/// of making up inputs to run them both on.
+pub trait Differential {
+    /// Everything needed to run both solvers once.
+    type Case: Clone + Debug;
+    /// What the solvers produce, compared for equality.
+    type Output: PartialEq + Debug;
+
+    const DAY: usize;
+    /// What's being checked, e.g. "tower height".
+    const NAME: &'static str;
+
+    /// A random valid case. `size` runs from 1 upwards and should roughly
+    /// control how big the case is, so that early cases are the easiest to
+    /// read.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Case;
+
+    /// Valid cases that are a little smaller than `case`, tried in order when
+    /// looking for the smallest case that still shows a mismatch.
+    fn shrink(&self, case: &Self::Case) -> Vec<Self::Case>;
+
+    fn optimized(&self, case: &Self::Case) -> Result<Self::Output, Box<dyn error::Error>>;
+    fn reference(&self, case: &Self::Case) -> Result<Self::Output, Box<dyn error::Error>>;
+}
+
+/// A case on which the two solvers disagreed, already shrunk.
+#[derive(Debug, Clone, Serialize)]
+pub struct Mismatch {
+    /// The seed the original case was generated from.
+    pub seed: u64,
+    pub size: usize,
+    pub case: String,
+    pub optimized: String,
+    pub reference: String,
+    /// How many smaller cases were found on the way to this one.
+    pub shrink_steps: usize,
+}
+
+/// Object-safe view of a `Differential`, which is what the runner and the
+/// registry in `days` deal in.
+pub trait AnyDifferential: Sync {
+    fn day(&self) -> usize;
+    fn name(&self) -> &'static str;
+
+    /// Generates the case for `seed` and `size`, and if the solvers disagree
+    /// on it, shrinks it as far as it will go.
+    fn check(&self, seed: u64, size: usize) -> Option<Mismatch>;
+}
+
+/// Runs `f`, turning errors and panics into descriptions that can be compared
+/// and printed like any other output.
+fn outcome<T: Debug>(f: impl FnOnce() -> Result<T, Box<dyn error::Error>>) -> Result<T, String> {
+    match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(format!("error: {}", e)),
+        Err(payload) => Err(format!("panic: {}", panic_message(payload))),
+    }
+}
+
+fn describe<T: Debug>(outcome: &Result<T, String>) -> String {
+    match outcome {
+        Ok(value) => format!("{:?}", value),
+        Err(e) => e.clone(),
+    }
+}
+
+impl<D: Differential + Sync> AnyDifferential for D {
+    fn day(&self) -> usize {
+        D::DAY
+    }
+
+    fn name(&self) -> &'static str {
+        D::NAME
+    }
+
+    fn check(&self, seed: u64, size: usize) -> Option<Mismatch> {
+        // Both answers for a case, if they differ
+        let disagreement = |case: &D::Case| {
+            let optimized = outcome(|| self.optimized(case));
+            let reference = outcome(|| self.reference(case));
+            match (&optimized, &reference) {
+                (Ok(a), Ok(b)) if a == b => None,
+                _ => Some((describe(&optimized), describe(&reference))),
+            }
+        };
+
+        let mut case = self.generate(&mut Rng::new(seed), size);
+        let mut answers = disagreement(&case)?;
+        verbose!(
+            "Day {} {}: seed {} disagrees, shrinking",
+            D::DAY,
+            D::NAME,
+            seed
+        );
+
+        let mut shrink_steps = 0;
+        'shrinking: loop {
+            for smaller in self.shrink(&case) {
+                if let Some(smaller_answers) = disagreement(&smaller) {
+                    case = smaller;
+                    answers = smaller_answers;
+                    shrink_steps += 1;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+
+        let (optimized, reference) = answers;
+        Some(Mismatch {
+            seed,
+            size,
+            case: format!("{:#?}", case),
+            optimized,
+            reference,
+            shrink_steps,
+        })
+    }
+}
+
+/// The seed for the `index`th case of a run started from `seed`. Mixed so that
+/// neighbouring runs don't share cases.
+pub fn case_seed(seed: u64, index: usize) -> u64 {
+    Rng::new(seed ^ (index as u64).wrapping_mul(0x2545_f491_4f6c_dd1d)).next_u64()
+}
+
+/// How one `Differential` fared over a run.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub day: usize,
+    pub name: &'static str,
+    pub seed: u64,
+    pub cases: usize,
+    /// The first case the solvers disagreed on, if any.
+    pub mismatch: Option<Mismatch>,
+}
+
+/// Checks `cases` cases, growing from size 1 up to `max_size`, stopping at the
+/// first mismatch.
+pub fn run(
+    differential: &dyn AnyDifferential,
+    seed: u64,
+    cases: usize,
+    max_size: usize,
+) -> RunReport {
+    // Keep the panic hook quiet, as the panics are reported as mismatches
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mismatch = (0..cases).find_map(|index| {
+        let size = 1 + index * max_size / cases;
+        differential.check(case_seed(seed, index), size)
+    });
+
+    panic::set_hook(hook);
+    RunReport {
+        day: differential.day(),
+        name: differential.name(),
+        seed,
+        cases,
+        mismatch,
+    }
+}
+
+pub fn print_reports(reports: &[RunReport]) {
+    for report in reports {
+        match &report.mismatch {
+            None => println!(
+                "Day {} {}: {} cases ok",
+                report.day, report.name, report.cases
+            ),
+            Some(m) => {
+                println!(
+                    "Day {} {}: MISMATCH on case seed {} (size {}, shrunk {} times)",
+                    report.day, report.name, m.seed, m.size, m.shrink_steps
+                );
+                println!("{}", m.case);
+                println!("  optimized: {}", m.optimized);
+                println!("  reference: {}", m.reference);
+            }
+        }
+    }
+}
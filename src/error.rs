@@ -0,0 +1,147 @@
+use std::{error, fmt, io, num::ParseIntError};
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// The input couldn't be read at all.
+    Io(io::Error),
+    /// Text that doesn't have the shape the puzzle describes.
+    Malformed(String),
+    /// A character or word that the day doesn't know the meaning of.
+    UnknownToken(String),
+    /// A name or index that doesn't match anything else in the input.
+    InvalidReference(String),
+}
+
+/// Why a puzzle input couldn't be parsed, and where in it the problem is.
+#[derive(Debug)]
+pub struct InputError {
+    pub kind: ErrorKind,
+    /// The file the input came from. Parsers only see the text, so this is
+    /// filled in by whoever read it.
+    pub file: Option<String>,
+    /// The 1-based line number and the text of the offending line, for errors
+    /// that can be pinned to one.
+    pub line: Option<(usize, String)>,
+    /// The 1-based column of the problem within that line, counted in
+    /// characters, where it's known.
+    pub column: Option<usize>,
+}
+
+impl InputError {
+    fn new(kind: ErrorKind) -> InputError {
+        InputError {
+            kind,
+            file: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    pub fn malformed(description: impl Into<String>) -> InputError {
+        InputError::new(ErrorKind::Malformed(description.into()))
+    }
+
+    pub fn unknown_token(token: impl ToString) -> InputError {
+        InputError::new(ErrorKind::UnknownToken(token.to_string()))
+    }
+
+    pub fn invalid_reference(description: impl Into<String>) -> InputError {
+        InputError::new(ErrorKind::InvalidReference(description.into()))
+    }
+
+    /// Pins the error to a line, unless it already has one. An unknown token
+    /// is also pinned to where it first appears in the line, if no column has
+    /// been given.
+    pub fn at(mut self, number: usize, text: &str) -> InputError {
+        if self.line.is_none() {
+            if let (None, ErrorKind::UnknownToken(token)) = (self.column, &self.kind) {
+                self.column = text
+                    .find(token.as_str())
+                    .map(|idx| text[..idx].chars().count() + 1);
+            }
+            self.line = Some((number, text.to_string()));
+        }
+        self
+    }
+
+    /// Pins the error to a 1-based column, unless it already has one.
+    pub fn at_column(mut self, column: usize) -> InputError {
+        if self.column.is_none() {
+            self.column = Some(column);
+        }
+        self
+    }
+
+    /// Moves the line number down by `lines`, for errors from parsing a piece
+    /// of the input that didn't start at the top.
+    pub fn shifted(mut self, lines: usize) -> InputError {
+        if let Some((number, _)) = &mut self.line {
+            *number += lines;
+        }
+        self
+    }
+
+    /// Moves the column right by `columns`, for errors from parsing a line
+    /// whose indentation was trimmed off.
+    pub fn indented(mut self, columns: usize) -> InputError {
+        if let Some(column) = &mut self.column {
+            *column += columns;
+        }
+        self
+    }
+
+    pub fn in_file(mut self, file: impl Into<String>) -> InputError {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.file, &self.line, self.column) {
+            (Some(file), Some((number, _)), Some(column)) => {
+                write!(f, "{}:{}:{}: ", file, number, column)?
+            }
+            (Some(file), Some((number, _)), None) => write!(f, "{}:{}: ", file, number)?,
+            (Some(file), None, _) => write!(f, "{}: ", file)?,
+            (None, Some((number, _)), Some(column)) => {
+                write!(f, "line {}, column {}: ", number, column)?
+            }
+            (None, Some((number, _)), None) => write!(f, "line {}: ", number)?,
+            (None, None, _) => (),
+        }
+
+        match &self.kind {
+            ErrorKind::Io(e) => write!(f, "{}", e)?,
+            ErrorKind::Malformed(description) => write!(f, "{}", description)?,
+            ErrorKind::UnknownToken(token) => write!(f, "unknown token {:?}", token)?,
+            ErrorKind::InvalidReference(description) => write!(f, "{}", description)?,
+        }
+
+        if let Some((_, text)) = &self.line {
+            write!(f, " in {:?}", text)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for InputError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for InputError {
+    fn from(e: io::Error) -> Self {
+        InputError::new(ErrorKind::Io(e))
+    }
+}
+
+impl From<ParseIntError> for InputError {
+    fn from(e: ParseIntError) -> Self {
+        InputError::malformed(format!("bad number ({})", e))
+    }
+}
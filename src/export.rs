@@ -0,0 +1,309 @@
+use std::{error, fmt::Write as _, fs, iter, path::Path};
+
+use crate::viewer::Simulation;
+
+/// A colour, as red, green and blue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+
+    /// Whether a black and white picture should show this as black.
+    fn is_dark(self) -> bool {
+        let Rgb(r, g, b) = self;
+        (299 * r as u32 + 587 * g as u32 + 114 * b as u32) < 128_000
+    }
+}
+
+/// Part of the way from `from` to `to`: `amount` out of `max`.
+pub fn blend(from: Rgb, to: Rgb, amount: usize, max: usize) -> Rgb {
+    let mix = |a: u8, b: u8| {
+        let (a, b, max) = (a as isize, b as isize, max.max(1) as isize);
+        (a + (b - a) * amount.min(max as usize) as isize / max) as u8
+    };
+    Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// The colours the days' text maps come out in. Empty space is white, and
+/// anything not listed is black.
+pub fn char_color(c: char) -> Rgb {
+    match c {
+        '.' => Rgb::WHITE,
+        // Off the edge of the map
+        ' ' => Rgb(224, 224, 224),
+        '#' => Rgb(96, 96, 96),
+        // Sand
+        'o' => Rgb(214, 178, 92),
+        // The rope's head, and where the path is going
+        'H' | '<' | '>' | '^' | 'v' => Rgb(208, 32, 32),
+        '1'..='9' | 'T' => Rgb(240, 140, 40),
+        // Where the rope's tail has been, and the path so far
+        '*' => Rgb(120, 180, 240),
+        'A'..='Z' | 'a'..='z' => Rgb(40, 80, 200),
+        _ => Rgb::BLACK,
+    }
+}
+
+/// A picture, one pixel per cell of whatever it shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    /// A text map drawn in `char_color`s, with short rows padded out in
+    /// white.
+    pub fn from_rows(rows: &[String]) -> Image {
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut image = Image::new(width, rows.len(), Rgb::WHITE);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                image.set(x, y, char_color(c));
+            }
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Colours one pixel. Anything outside the picture is left out.
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    /// Copies `other` in with its top left corner at `(x, y)`.
+    fn paste(&mut self, other: &Image, x: usize, y: usize) {
+        for oy in 0..other.height {
+            for ox in 0..other.width {
+                self.set(x + ox, y + oy, other.get(ox, oy));
+            }
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Rgb]> {
+        self.pixels.chunks(self.width.max(1))
+    }
+
+    /// Each pixel of a row `scale` times over, `scale` times.
+    fn scaled_rows(&self, scale: usize) -> impl Iterator<Item = Vec<Rgb>> + '_ {
+        self.rows().flat_map(move |row| {
+            let wide: Vec<Rgb> = row
+                .iter()
+                .flat_map(|&color| iter::repeat_n(color, scale))
+                .collect();
+            iter::repeat_n(wide, scale)
+        })
+    }
+}
+
+/// The file formats images can be written in, none of which need anything
+/// beyond the standard library to produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Binary portable pixmap, in full colour.
+    Ppm,
+    /// Binary portable bitmap, in black and white.
+    Pbm,
+    Svg,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "ppm" => Some(Format::Ppm),
+            "pbm" => Some(Format::Pbm),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Pbm => "pbm",
+            Format::Svg => "svg",
+        }
+    }
+}
+
+/// `image` as the contents of a file in `format`, with each pixel blown up to
+/// `scale` by `scale`.
+pub fn encode(image: &Image, format: Format, scale: usize) -> Vec<u8> {
+    let (width, height) = (image.width * scale, image.height * scale);
+    match format {
+        Format::Ppm => {
+            let mut bytes = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+            for row in image.scaled_rows(scale) {
+                for Rgb(r, g, b) in row {
+                    bytes.extend_from_slice(&[r, g, b]);
+                }
+            }
+            bytes
+        }
+        Format::Pbm => {
+            let mut bytes = format!("P4\n{} {}\n", width, height).into_bytes();
+            for row in image.scaled_rows(scale) {
+                // Eight pixels to a byte, the first in the highest bit
+                for chunk in row.chunks(8) {
+                    let bits = chunk
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, color)| color.is_dark())
+                        .fold(0u8, |byte, (idx, _)| byte | 0x80 >> idx);
+                    bytes.push(bits);
+                }
+            }
+            bytes
+        }
+        Format::Svg => {
+            let mut svg = format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+                 viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+                width, height, image.width, image.height
+            );
+            // One rectangle for each run of the same colour along a row
+            for (y, row) in image.rows().enumerate() {
+                let mut x = 0;
+                while x < row.len() {
+                    let run = row[x..].iter().take_while(|&&c| c == row[x]).count();
+                    let Rgb(r, g, b) = row[x];
+                    let _ = writeln!(
+                        svg,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" \
+                         fill=\"#{:02x}{:02x}{:02x}\"/>",
+                        x, y, run, r, g, b
+                    );
+                    x += run;
+                }
+            }
+            svg.push_str("</svg>\n");
+            svg.into_bytes()
+        }
+    }
+}
+
+/// The first `steps` steps of `simulation` as pictures, starting with how it
+/// looks before the first one. The frames are all the same size, with the
+/// maps lined up by their `origin`s, so that they can be played as an
+/// animation.
+pub fn animate(simulation: Box<dyn Simulation>, steps: usize) -> impl Iterator<Item = Image> {
+    // A dry run to find how much of the map the frames cover between them
+    let mut dry_run = simulation.clone_box();
+    let mut covered: Option<(isize, isize, isize, isize)> = None;
+    for step in 0..=steps {
+        let (x, y) = dry_run.origin();
+        let rows = dry_run.rows();
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0) as isize;
+        let (right, bottom) = (x + width, y + rows.len() as isize);
+        covered = Some(match covered {
+            Some((min_x, min_y, max_x, max_y)) => (
+                min_x.min(x),
+                min_y.min(y),
+                max_x.max(right),
+                max_y.max(bottom),
+            ),
+            None => (x, y, right, bottom),
+        });
+        if step == steps || !dry_run.step() {
+            break;
+        }
+    }
+    let (min_x, min_y, max_x, max_y) = covered.unwrap();
+
+    let mut simulation = Some(simulation);
+    let mut step = 0;
+    iter::from_fn(move || {
+        let sim = simulation.as_mut()?;
+        let mut frame = Image::new(
+            (max_x - min_x) as usize,
+            (max_y - min_y) as usize,
+            Rgb::WHITE,
+        );
+        let (x, y) = sim.origin();
+        frame.paste(
+            &Image::from_rows(&sim.rows()),
+            (x - min_x) as usize,
+            (y - min_y) as usize,
+        );
+        if step == steps || !sim.step() {
+            simulation = None;
+        }
+        step += 1;
+        Some(frame)
+    })
+}
+
+/// How to write out a day's pictures.
+#[derive(Debug, Clone, Copy)]
+pub struct ExportOptions {
+    pub format: Format,
+    pub scale: usize,
+    /// Only every this many frames is written, along with the last one.
+    pub every: usize,
+}
+
+/// Writes `frames` into `dir` as `<stem>.<ext>` if there's only one of them,
+/// or numbered from `<stem>-00000.<ext>` if there are more. Returns the paths
+/// written.
+pub fn write_frames(
+    frames: impl Iterator<Item = Image>,
+    dir: &Path,
+    stem: &str,
+    options: ExportOptions,
+) -> Result<Vec<String>, Box<dyn error::Error>> {
+    fs::create_dir_all(dir).map_err(|e| format!("Couldn't create {}: {}", dir.display(), e))?;
+    let ext = options.format.extension();
+
+    let mut frames = frames.enumerate().peekable();
+    let mut written = Vec::new();
+    let mut numbered = false;
+    while let Some((idx, frame)) = frames.next() {
+        let is_last = frames.peek().is_none();
+        if idx % options.every != 0 && !is_last {
+            continue;
+        }
+        numbered |= !is_last;
+        let name = if numbered {
+            format!("{}-{:05}.{}", stem, written.len(), ext)
+        } else {
+            format!("{}.{}", stem, ext)
+        };
+        let path = dir.join(name);
+        fs::write(&path, encode(&frame, options.format, options.scale))
+            .map_err(|e| format!("Couldn't write {}: {}", path.display(), e))?;
+        written.push(path.display().to_string());
+    }
+    Ok(written)
+}
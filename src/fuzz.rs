@@ -0,0 +1,204 @@
+use std::{fs, panic};
+
+use serde::Serialize;
+
+use crate::{
+    differential::{case_seed, Rng},
+    solution::{self, AnySolution},
+    verify::panic_message,
+};
+
+/// Characters worth throwing at the parsers besides the ones already in the
+/// input: separators, signs, digits, and a few that are more than one byte
+/// long, to catch slicing in the middle of a character.
+const EXTRA_CHARS: &str = " \n\t-+0123456789:,;=.()[]{}#<>aAzZ_é✓\u{0}";
+
+/// Numbers that tend to find overflows and off-by-ones.
+const EXTREME_NUMBERS: [&str; 9] = [
+    "0",
+    "-1",
+    "1",
+    "256",
+    "4294967296",
+    "9223372036854775807",
+    "-9223372036854775808",
+    "18446744073709551615",
+    "99999999999999999999",
+];
+
+/// Makes a handful of random edits to `input`.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let alphabet: Vec<char> = input.chars().chain(EXTRA_CHARS.chars()).collect();
+    let mut chars: Vec<char> = input.chars().collect();
+
+    for _ in 0..1 + rng.index(4) {
+        let len = chars.len();
+        let pos = rng.index(len + 1);
+        let end = (pos + 1 + rng.index(8)).min(len);
+        match rng.index(8) {
+            0 if pos < len => chars[pos] = alphabet[rng.index(alphabet.len())],
+            1 => chars.insert(pos, alphabet[rng.index(alphabet.len())]),
+            2 if pos < len => {
+                chars.drain(pos..end);
+            }
+            3 if pos < len => {
+                let copy: Vec<char> = chars[pos..end].to_vec();
+                chars.splice(pos..pos, copy);
+            }
+            4 => chars.truncate(pos),
+            5 => {
+                let number = EXTREME_NUMBERS[rng.index(EXTREME_NUMBERS.len())];
+                // Replace the number (if any) starting here
+                let digits = chars[pos..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count();
+                chars.splice(pos..pos + digits, number.chars());
+            }
+            6 => {
+                let mut lines: Vec<String> = chars
+                    .iter()
+                    .collect::<String>()
+                    .lines()
+                    .map(String::from)
+                    .collect();
+                if !lines.is_empty() {
+                    let line = rng.index(lines.len());
+                    if rng.chance(1, 2) {
+                        lines.remove(line);
+                    } else {
+                        let copy = lines[line].clone();
+                        lines.insert(rng.index(lines.len() + 1), copy);
+                    }
+                    chars = lines.join("\n").chars().collect();
+                }
+            }
+            _ => {
+                let mut lines: Vec<String> = chars
+                    .iter()
+                    .collect::<String>()
+                    .lines()
+                    .map(String::from)
+                    .collect();
+                if lines.len() > 1 {
+                    let a = rng.index(lines.len());
+                    let b = rng.index(lines.len());
+                    lines.swap(a, b);
+                    chars = lines.join("\n").chars().collect();
+                }
+            }
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+/// An input that made a parser panic, shrunk as far as it will go.
+#[derive(Debug, Clone, Serialize)]
+pub struct Crash {
+    pub day: usize,
+    /// The seed of the case that first found it.
+    pub seed: u64,
+    pub input: String,
+    pub message: String,
+}
+
+/// How one day's parser fared over a run.
+#[derive(Debug, Clone, Serialize)]
+pub struct FuzzReport {
+    pub day: usize,
+    pub cases: usize,
+    pub crash: Option<Crash>,
+}
+
+/// The panic message if parsing `input` panics. Errors are fine.
+fn parse_panics(solution: &dyn AnySolution, input: &str) -> Option<String> {
+    panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let _ = solution.parse(input);
+    }))
+    .err()
+    .map(panic_message)
+}
+
+/// Smaller inputs to try when shrinking: without each line, then without
+/// each character.
+fn smaller_inputs(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let without_lines = (0..lines.len()).map(move |idx| {
+        let mut fewer = lines.clone();
+        fewer.remove(idx);
+        fewer.join("\n")
+    });
+    let without_chars = input.char_indices().map(move |(idx, c)| {
+        let mut shorter = input.to_string();
+        shorter.replace_range(idx..idx + c.len_utf8(), "");
+        shorter
+    });
+    without_lines.chain(without_chars).collect()
+}
+
+/// The smallest input found that still panics, and its message.
+fn shrink(solution: &dyn AnySolution, mut input: String, mut message: String) -> (String, String) {
+    'shrinking: loop {
+        for smaller in smaller_inputs(&input) {
+            if let Some(smaller_message) = parse_panics(solution, &smaller) {
+                input = smaller;
+                message = smaller_message;
+                continue 'shrinking;
+            }
+        }
+        break;
+    }
+
+    (input, message)
+}
+
+/// Parses `cases` mutations of the day's sample input, and of the empty
+/// input, stopping at the first that panics.
+pub fn run(solution: &dyn AnySolution, seed: u64, cases: usize) -> FuzzReport {
+    let corpus = [
+        String::new(),
+        fs::read_to_string(solution::sample_path(solution.day())).unwrap_or_default(),
+    ];
+
+    // Keep the panic hook quiet, as the panics are reported as crashes
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let crash = (0..cases).find_map(|index| {
+        let seed = case_seed(seed, index);
+        let mut rng = Rng::new(seed);
+        let original = &corpus[rng.index(corpus.len())];
+        let input = mutate(&mut rng, original);
+        let message = parse_panics(solution, &input)?;
+        let (input, message) = shrink(solution, input, message);
+        Some(Crash {
+            day: solution.day(),
+            seed,
+            input,
+            message,
+        })
+    });
+
+    panic::set_hook(hook);
+    FuzzReport {
+        day: solution.day(),
+        cases,
+        crash,
+    }
+}
+
+pub fn print_reports(reports: &[FuzzReport]) {
+    for report in reports {
+        match &report.crash {
+            None => println!("Day {}: {} inputs ok", report.day, report.cases),
+            Some(crash) => {
+                println!(
+                    "Day {}: PANIC on case seed {}: {}",
+                    report.day, crash.seed, crash.message
+                );
+                println!("{:?}", crash.input);
+            }
+        }
+    }
+}
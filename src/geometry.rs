@@ -0,0 +1,551 @@
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// The integer types that points can be made of.
+pub trait Coord:
+    Copy + Ord + Hash + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+/// Coordinates that can go negative, and so can be used as offsets.
+pub trait SignedCoord: Coord + Neg<Output = Self> {
+    fn signum(self) -> Self {
+        match self.cmp(&Self::ZERO) {
+            std::cmp::Ordering::Less => -Self::ONE,
+            std::cmp::Ordering::Equal => Self::ZERO,
+            std::cmp::Ordering::Greater => Self::ONE,
+        }
+    }
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(impl Coord for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl SignedCoord for i8 {}
+impl SignedCoord for i16 {}
+impl SignedCoord for i32 {}
+impl SignedCoord for i64 {}
+impl SignedCoord for isize {}
+
+/// A point or offset on a 2D plane. As with `Grid`, y increases downwards, so
+/// "clockwise" is as seen on the screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Coord> Point2<T> {
+    pub fn manhattan(self, other: Point2<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance when diagonal steps are allowed.
+    pub fn chebyshev(self, other: Point2<T>) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl<T: SignedCoord> Point2<T> {
+    /// Reduces each component to -1, 0 or 1; a single step towards where this
+    /// offset points.
+    pub fn signum(self) -> Point2<T> {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Rotates a quarter turn clockwise about the origin.
+    pub fn rotate_cw(self) -> Point2<T> {
+        Point2::new(-self.y, self.x)
+    }
+
+    /// Rotates a quarter turn counterclockwise about the origin.
+    pub fn rotate_ccw(self) -> Point2<T> {
+        Point2::new(self.y, -self.x)
+    }
+
+    pub fn neighbours4(self) -> impl Iterator<Item = Point2<T>> {
+        Direction::ALL.iter().map(move |dir| self + dir.offset())
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Point2<T>> {
+        Direction8::ALL.iter().map(move |dir| self + dir.offset())
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T: Coord> Add for Point2<T> {
+    type Output = Point2<T>;
+
+    fn add(self, rhs: Point2<T>) -> Point2<T> {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coord> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Point2<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coord> Sub for Point2<T> {
+    type Output = Point2<T>;
+
+    fn sub(self, rhs: Point2<T>) -> Point2<T> {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Coord> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Point2<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coord> Mul<T> for Point2<T> {
+    type Output = Point2<T>;
+
+    fn mul(self, rhs: T) -> Point2<T> {
+        Point2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: SignedCoord> Neg for Point2<T> {
+    type Output = Point2<T>;
+
+    fn neg(self) -> Point2<T> {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    pub fn manhattan(self, other: Point3<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(self, other: Point3<T>) -> T {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+}
+
+impl<T: SignedCoord> Point3<T> {
+    /// The six points sharing a face with this one.
+    pub fn neighbours6(self) -> impl Iterator<Item = Point3<T>> {
+        let (one, zero) = (T::ONE, T::ZERO);
+        let offsets = [
+            Point3::new(-one, zero, zero),
+            Point3::new(one, zero, zero),
+            Point3::new(zero, -one, zero),
+            Point3::new(zero, one, zero),
+            Point3::new(zero, zero, -one),
+            Point3::new(zero, zero, one),
+        ];
+        IntoIterator::into_iter(offsets).map(move |offset| self + offset)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Coord> Add for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, rhs: Point3<T>) -> Point3<T> {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Coord> Sub for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, rhs: Point3<T>) -> Point3<T> {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Coord> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, rhs: T) -> Point3<T> {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/// One of the four orthogonal directions on the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Reads an arrow (`^>v<`) or the first letter of the direction's name.
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '^' | 'U' => Some(Direction::Up),
+            '>' | 'R' => Some(Direction::Right),
+            'v' | 'V' | 'D' => Some(Direction::Down),
+            '<' | 'L' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn to_arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+
+    /// A single step in this direction.
+    pub fn offset<T: SignedCoord>(self) -> Point2<T> {
+        match self {
+            Direction::Up => Point2::new(T::ZERO, -T::ONE),
+            Direction::Right => Point2::new(T::ONE, T::ZERO),
+            Direction::Down => Point2::new(T::ZERO, T::ONE),
+            Direction::Left => Point2::new(-T::ONE, T::ZERO),
+        }
+    }
+
+    pub fn turn_cw(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_ccw(self) -> Direction {
+        self.reverse().turn_cw()
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.turn_cw().turn_cw()
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+}
+
+/// One of the eight directions to an adjacent cell, including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    pub fn offset<T: SignedCoord>(self) -> Point2<T> {
+        use Direction::*;
+        match self {
+            Direction8::Up => Up.offset(),
+            Direction8::UpRight => Up.offset() + Right.offset(),
+            Direction8::Right => Right.offset(),
+            Direction8::DownRight => Down.offset() + Right.offset(),
+            Direction8::Down => Down.offset(),
+            Direction8::DownLeft => Down.offset() + Left.offset(),
+            Direction8::Left => Left.offset(),
+            Direction8::UpLeft => Up.offset() + Left.offset(),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => Direction8::Up,
+            Direction::Right => Direction8::Right,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+        }
+    }
+}
+
+/// The smallest box, inclusive at both ends, holding a set of 2D points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox2<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coord> BoundingBox2<T> {
+    /// `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point2<T>>) -> Option<BoundingBox2<T>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = BoundingBox2 {
+            min: first,
+            max: first,
+        };
+        for p in points {
+            bounds.include(p);
+        }
+        Some(bounds)
+    }
+
+    /// Grows the box, if needed, to hold `p`.
+    pub fn include(&mut self, p: Point2<T>) {
+        self.min = Point2::new(self.min.x.min(p.x), self.min.y.min(p.y));
+        self.max = Point2::new(self.max.x.max(p.x), self.max.y.max(p.y));
+    }
+
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// Adds a margin of `by` on every side.
+    pub fn expand(&self, by: T) -> BoundingBox2<T> {
+        let margin = Point2::new(by, by);
+        BoundingBox2 {
+            min: self.min - margin,
+            max: self.max + margin,
+        }
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+}
+
+/// The smallest box, inclusive at both ends, holding a set of 3D points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox3<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T: Coord> BoundingBox3<T> {
+    /// `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point3<T>>) -> Option<BoundingBox3<T>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = BoundingBox3 {
+            min: first,
+            max: first,
+        };
+        for p in points {
+            bounds.include(p);
+        }
+        Some(bounds)
+    }
+
+    pub fn include(&mut self, p: Point3<T>) {
+        self.min = Point3::new(
+            self.min.x.min(p.x),
+            self.min.y.min(p.y),
+            self.min.z.min(p.z),
+        );
+        self.max = Point3::new(
+            self.max.x.max(p.x),
+            self.max.y.max(p.y),
+            self.max.z.max(p.z),
+        );
+    }
+
+    pub fn contains(&self, p: Point3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    pub fn expand(&self, by: T) -> BoundingBox3<T> {
+        let margin = Point3::new(by, by, by);
+        BoundingBox3 {
+            min: self.min - margin,
+            max: self.max + margin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_arithmetic() {
+        let mut p = Point2::new(3, -2);
+        assert_eq!(p + Point2::new(1, 5), Point2::new(4, 3));
+        assert_eq!(p - Point2::new(1, 5), Point2::new(2, -7));
+        assert_eq!(p * 3, Point2::new(9, -6));
+        assert_eq!(-p, Point2::new(-3, 2));
+        p += Point2::new(1, 1);
+        p -= Point2::new(0, 2);
+        assert_eq!(p, Point2::new(4, -3));
+        assert_eq!(p.signum(), Point2::new(1, -1));
+        assert_eq!(Point2::new(0, 7).signum(), Point2::new(0, 1));
+
+        let q = Point3::new(1u8, 2, 3);
+        assert_eq!(q + q * 2 - Point3::new(1, 1, 1), Point3::new(2, 5, 8));
+        assert_eq!(<(i32, i32)>::from(Point2::from((5, 6))), (5, 6));
+    }
+
+    #[test]
+    fn rotation_is_on_screen() {
+        // Up on the screen is negative y, so a clockwise turn from up is right
+        let up: Point2<i32> = Direction::Up.offset();
+        assert_eq!(up.rotate_cw(), Direction::Right.offset());
+        assert_eq!(up.rotate_ccw(), Direction::Left.offset());
+        for dir in Direction::ALL.iter() {
+            let offset: Point2<i32> = dir.offset();
+            assert_eq!(offset.rotate_cw(), dir.turn_cw().offset());
+            assert_eq!(offset.rotate_ccw(), dir.turn_ccw().offset());
+            assert_eq!(-offset, dir.reverse().offset());
+        }
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(1usize, 10);
+        let b = Point2::new(4usize, 2);
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(b.manhattan(a), 11);
+        assert_eq!(a.chebyshev(b), 8);
+        assert_eq!(a.manhattan(a), 0);
+
+        let c = Point3::new(-1i64, 0, 5);
+        assert_eq!(c.manhattan(Point3::new(2, -2, 5)), 5);
+        assert_eq!(c.chebyshev(Point3::new(2, -2, 5)), 3);
+    }
+
+    #[test]
+    fn neighbours() {
+        let p = Point2::new(0i32, 0);
+        let around: Vec<_> = p.neighbours4().collect();
+        assert_eq!(
+            around,
+            vec![
+                Point2::new(0, -1),
+                Point2::new(1, 0),
+                Point2::new(0, 1),
+                Point2::new(-1, 0)
+            ]
+        );
+
+        let mut all: Vec<_> = p.neighbours8().collect();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 8);
+        assert!(all.iter().all(|&n| p.chebyshev(n) == 1));
+
+        let faces: Vec<_> = Point3::new(0i8, 0, 0).neighbours6().collect();
+        assert_eq!(faces.len(), 6);
+        assert!(faces.iter().all(|&n| n.manhattan(Point3::default()) == 1));
+    }
+
+    #[test]
+    fn bounding_boxes() {
+        assert_eq!(BoundingBox2::<i32>::from_points(vec![]), None);
+        let bounds = BoundingBox2::from_points(vec![
+            Point2::new(2, -1),
+            Point2::new(-3, 4),
+            Point2::new(0, 0),
+        ])
+        .unwrap();
+        assert_eq!(bounds.min, Point2::new(-3, -1));
+        assert_eq!(bounds.max, Point2::new(2, 4));
+        assert_eq!((bounds.width(), bounds.height(), bounds.area()), (6, 6, 36));
+        assert!(bounds.contains(Point2::new(2, 4)));
+        assert!(!bounds.contains(Point2::new(3, 0)));
+        assert!(bounds.expand(1).contains(Point2::new(3, 0)));
+    }
+}
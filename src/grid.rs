@@ -0,0 +1,516 @@
+use std::{
+    collections::HashMap,
+    error,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    error::InputError,
+    geometry::{BoundingBox2, Point2},
+};
+
+/// A dense, rectangular 2D grid stored row by row. `(x, y)` is column `x` of
+/// row `y`, with `(0, 0)` in the top left as the input is written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from rows which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, InputError> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(InputError::malformed(format!(
+                "row {} has length {}, expected {}",
+                y + 1,
+                row.len(),
+                width
+            )));
+        }
+
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a character map, one row per line, converting each character
+    /// with `f`. Blank lines are skipped, and every row must be the same width.
+    pub fn parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, InputError>,
+    ) -> Result<Grid<T>, InputError> {
+        let mut width = None;
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim_end()))
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(number, line)| {
+                let row: Vec<T> = line
+                    .chars()
+                    .enumerate()
+                    .map(|(x, c)| f(c).map_err(|e| e.at_column(x + 1)))
+                    .collect::<Result<_, _>>()
+                    .map_err(|e| e.at(number, line))?;
+                let expected = *width.get_or_insert(row.len());
+                if row.len() != expected {
+                    return Err(InputError::malformed(format!(
+                        "row has length {}, expected {}",
+                        row.len(),
+                        expected
+                    ))
+                    .at(number, line));
+                }
+                Ok(row)
+            })
+            .collect::<Result<_, _>>()?;
+        Grid::from_rows(rows)
+    }
+
+    /// Like `parse`, but for maps whose lines have ragged ends; short rows are
+    /// filled out with `pad`. Blank lines are kept as rows of padding.
+    pub fn parse_padded(
+        input: &str,
+        pad: T,
+        mut f: impl FnMut(char) -> Result<T, InputError>,
+    ) -> Result<Grid<T>, InputError>
+    where
+        T: Clone,
+    {
+        let mut rows: Vec<Vec<T>> = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| f(c).map_err(|e| e.at_column(x + 1)))
+                    .collect::<Result<_, _>>()
+                    .map_err(|e| e.at(idx + 1, line))
+            })
+            .collect::<Result<_, _>>()?;
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        for row in rows.iter_mut() {
+            row.resize(width, pad.clone());
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// Checked lookup; anything outside the grid is `None`.
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        if self.in_bounds(x, y) {
+            Some(&self.cells[y as usize * self.width + x as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        if self.in_bounds(x, y) {
+            Some(&mut self.cells[y as usize * self.width + x as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Appends a row below the current last one. The first row pushed onto an
+    /// empty grid sets its width.
+    pub fn push_row(&mut self, row: Vec<T>) -> Result<(), Box<dyn error::Error>> {
+        if self.height == 0 {
+            self.width = row.len();
+        } else if row.len() != self.width {
+            return Err(format!("Row has length {}, expected {}", row.len(), self.width).into());
+        }
+        self.cells.extend(row);
+        self.height += 1;
+        Ok(())
+    }
+
+    fn in_bounds_filter(
+        &self,
+        points: impl Iterator<Item = Point2<isize>>,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        points.filter_map(move |p| {
+            if p.x >= 0 && p.y >= 0 && (p.x as usize) < width && (p.y as usize) < height {
+                Some((p.x as usize, p.y as usize))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The orthogonally adjacent positions that are inside the grid.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.in_bounds_filter(Point2::new(x as isize, y as isize).neighbours4())
+    }
+
+    /// The orthogonally and diagonally adjacent positions inside the grid.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.in_bounds_filter(Point2::new(x as isize, y as isize).neighbours8())
+    }
+
+    /// Checked row lookup; a row below the grid is `None`.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        if y < self.height {
+            Some(&mut self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// Checked column lookup, top to bottom; a column right of the grid is
+    /// `None`.
+    pub fn column(
+        &self,
+        x: usize,
+    ) -> Option<impl DoubleEndedIterator<Item = &T> + ExactSizeIterator> {
+        if x < self.width {
+            Some(self.cells.iter().skip(x).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    pub fn column_mut(
+        &mut self,
+        x: usize,
+    ) -> Option<impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator> {
+        if x < self.width {
+            let width = self.width;
+            Some(self.cells.iter_mut().skip(x).step_by(width))
+        } else {
+            None
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(move |x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws the grid as text, one line per row.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside the {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2<usize>) -> &T {
+        &self[(p.x, p.y)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside the {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    fn index_mut(&mut self, p: Point2<usize>) -> &mut T {
+        &mut self[(p.x, p.y)]
+    }
+}
+
+/// A grid with no fixed bounds, for worlds that sprawl or grow as the puzzle
+/// runs. Only occupied positions are stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(isize, isize), T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    /// Parses a character map, keeping only the characters `f` returns a value
+    /// for. The top left character is at `(0, 0)`.
+    pub fn parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<Option<T>, InputError>,
+    ) -> Result<SparseGrid<T>, InputError> {
+        let mut grid = SparseGrid::new();
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(value) = f(c).map_err(|e| e.at_column(x + 1).at(y + 1, line))? {
+                    grid.insert(x as isize, y as isize, value);
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.cells.get(&(x, y))
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        self.cells.get_mut(&(x, y))
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        self.cells.contains_key(&(x, y))
+    }
+
+    pub fn insert(&mut self, x: isize, y: isize, value: T) -> Option<T> {
+        self.cells.insert((x, y), value)
+    }
+
+    pub fn remove(&mut self, x: isize, y: isize) -> Option<T> {
+        self.cells.remove(&(x, y))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((isize, isize), &T)> {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (isize, isize)> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The smallest box containing every occupied position, or `None` if the
+    /// grid is empty.
+    pub fn bounds(&self) -> Option<BoundingBox2<isize>> {
+        BoundingBox2::from_points(self.positions().map(Point2::from))
+    }
+
+    /// Draws the bounding box of the occupied cells, with `f` given `None` for
+    /// empty positions.
+    pub fn render(&self, mut f: impl FnMut(Option<&T>) -> char) -> String {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+        (bounds.min.y..=bounds.max.y)
+            .map(|y| {
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| f(self.get(x, y)))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::parse("abc\ndef\n", Ok).unwrap()
+    }
+
+    #[test]
+    fn lookups_in_and_out_of_bounds() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(0, 0), Some(&'a'));
+        assert_eq!(grid.get(2, 1), Some(&'f'));
+        for &(x, y) in &[(-1, 0), (0, -1), (3, 0), (0, 2), (isize::MIN, isize::MAX)] {
+            assert_eq!(grid.get(x, y), None, "({}, {})", x, y);
+        }
+        assert_eq!(grid[(1, 1)], 'e');
+        assert_eq!(grid[Point2::new(2, 0)], 'c');
+
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).unwrap().collect::<String>(), "be");
+        assert!(grid.column(3).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside the 3x2 grid")]
+    fn indexing_out_of_bounds() {
+        let _ = sample()[(3, 0)];
+    }
+
+    #[test]
+    fn changing_cells() {
+        let mut grid = sample();
+        *grid.get_mut(1, 0).unwrap() = 'B';
+        assert_eq!(grid.get_mut(1, 2), None);
+        grid.row_mut(1).unwrap()[0] = 'D';
+        assert!(grid.row_mut(5).is_none());
+        *grid.column_mut(1).unwrap().next_back().unwrap() = 'E';
+        assert!(grid.column_mut(3).is_none());
+        grid[(2, 1)] = 'F';
+        assert_eq!(grid.render(|&c| c), "aBc\nDEF");
+    }
+
+    #[test]
+    fn rows_must_line_up() {
+        let error = Grid::parse("abc\nde\n", Ok).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: row has length 2, expected 3 in \"de\""
+        );
+
+        let padded = Grid::parse_padded("ab\n\nc", '.', Ok).unwrap();
+        assert_eq!(padded.render(|&c| c), "ab\n..\nc.");
+
+        let mut grid = Grid::new(0, 0, 0);
+        grid.push_row(vec![1, 2]).unwrap();
+        assert!(grid.push_row(vec![3]).is_err());
+        assert_eq!((grid.width(), grid.height()), (2, 1));
+    }
+
+    #[test]
+    fn neighbours_stop_at_the_edges() {
+        let grid = Grid::new(3, 3, ());
+        let mut corner: Vec<_> = grid.neighbours4(0, 0).collect();
+        corner.sort();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8(0, 2).count(), 3);
+        assert_eq!(grid.neighbours4(1, 1).count(), 4);
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+    }
+
+    #[test]
+    fn sparse_bounds_and_rendering() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render(|_| '#'), "");
+
+        grid.insert(-1, 2, 'a');
+        grid.insert(2, 0, 'b');
+        assert_eq!(
+            grid.bounds(),
+            Some(BoundingBox2 {
+                min: Point2::new(-1, 0),
+                max: Point2::new(2, 2),
+            })
+        );
+        assert_eq!(
+            grid.render(|cell| cell.copied().unwrap_or('.')),
+            "...b\n....\na..."
+        );
+
+        assert_eq!(grid.remove(2, 0), Some('b'));
+        assert_eq!(grid.render(|cell| cell.copied().unwrap_or('.')), "a");
+        assert!(!grid.contains(2, 0));
+    }
+
+    #[test]
+    fn sparse_parsing_keeps_what_is_asked_for() {
+        let grid = SparseGrid::parse(".#\n#.", |c| Ok(Some(c).filter(|&c| c == '#'))).unwrap();
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains(1, 0) && grid.contains(0, 1));
+
+        let error = SparseGrid::<char>::parse("..\n.x", |c| match c {
+            '.' => Ok(None),
+            c => Err(InputError::unknown_token(c)),
+        })
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: unknown token \"x\" in \".x\""
+        );
+    }
+}
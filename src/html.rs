@@ -0,0 +1,238 @@
+use std::{fmt::Write, time::Duration};
+
+use serde_json::Value;
+
+use crate::{
+    bench::format_duration,
+    export::{self, Format, Image},
+    report::{self, PhaseReport, SolveReport},
+    solution::AnySolution,
+};
+
+/// The most pictures shown of any one part. Animations with more frames than
+/// this are shown by an evenly spread selection of them, ending with the last.
+pub const MAX_PICTURES: usize = 4;
+
+/// How many pixels wide each cell of a picture is.
+const SCALE: usize = 2;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: left; vertical-align: top; }
+td.time { text-align: right; font-variant-numeric: tabular-nums; }
+.error { color: #b00; }
+pre { margin: 0; line-height: 1; }
+figure { display: inline-block; margin: 0 1em 1em 0; }
+figure svg { max-width: 40em; max-height: 40em; width: auto; height: auto; border: 1px solid #ccc; }
+";
+
+/// One of a day's pictures, as an SVG document.
+#[derive(Debug, Clone)]
+pub struct Picture {
+    pub part: usize,
+    /// Which frame of the part's pictures or animation this is, from 0.
+    pub frame: usize,
+    pub svg: String,
+}
+
+/// Everything the page shows about one day.
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub report: SolveReport,
+    pub pictures: Vec<Picture>,
+}
+
+/// Solves both parts of `input` and draws them, animating any simulation for
+/// up to `steps` steps. The input is parsed a second time for the pictures, so
+/// that drawing doesn't count towards the timings.
+pub fn day_report(
+    solution: &dyn AnySolution,
+    input_name: &str,
+    input: &str,
+    steps: usize,
+) -> DayReport {
+    let report = report::solve(solution, input_name, input, &[1, 2]);
+    let mut pictures = Vec::new();
+    if let Ok(parsed) = solution.parse(input) {
+        for part in 1..=2 {
+            // Days with nothing to draw are fine
+            if let Ok(frames) = solution.frames(&*parsed, part, steps) {
+                pictures.extend(spread(frames).into_iter().map(|(frame, image)| Picture {
+                    part,
+                    frame,
+                    svg: String::from_utf8(export::encode(&image, Format::Svg, SCALE)).unwrap(),
+                }));
+            }
+        }
+    }
+    DayReport { report, pictures }
+}
+
+/// Up to `MAX_PICTURES` of `frames`, roughly evenly spaced, plus the last,
+/// with their numbers. Only the ones that might be kept are held on to.
+fn spread(frames: impl Iterator<Item = Image>) -> Vec<(usize, Image)> {
+    let mut kept = Vec::new();
+    let mut last = None;
+    let mut stride = 1;
+    for (idx, frame) in frames.enumerate() {
+        if idx % stride != 0 {
+            last = Some((idx, frame));
+            continue;
+        }
+        last = None;
+        kept.push((idx, frame));
+        if kept.len() > MAX_PICTURES {
+            kept = kept.into_iter().step_by(2).collect();
+            stride *= 2;
+        }
+    }
+    kept.extend(last);
+    kept
+}
+
+/// `text` with the characters that mean something in HTML escaped.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A diagnostic's value as plain text, with objects such as cycles spelled
+/// out field by field.
+fn describe(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "none".to_string(),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(name, value)| format!("{} {}", name, describe(value)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        value => value.to_string(),
+    }
+}
+
+fn time(phase: &PhaseReport) -> String {
+    format_duration(Duration::from_nanos(phase.elapsed_ns))
+}
+
+/// A part's answer for the summary table, where answers over several lines
+/// are left to the day's own section.
+fn short_answer(report: &SolveReport, part: usize) -> String {
+    let phase = match report.parts.iter().find(|p| p.part == Some(part)) {
+        Some(phase) => phase,
+        None if report.parse.error.is_some() => {
+            return "<span class=\"error\">parse error</span>".to_string()
+        }
+        None => return "-".to_string(),
+    };
+    match (&phase.answer, &phase.error) {
+        (Some(answer), _) if answer.contains('\n') => {
+            format!("<a href=\"#day{}\">(see below)</a>", report.day)
+        }
+        (Some(answer), _) => escape(answer),
+        (None, _) => "<span class=\"error\">error</span>".to_string(),
+    }
+}
+
+fn write_phase(html: &mut String, name: &str, phase: &PhaseReport) {
+    let result = match (&phase.answer, &phase.error) {
+        (_, Some(error)) => format!("<span class=\"error\">{}</span>", escape(error)),
+        (Some(answer), None) => format!("<pre>{}</pre>", escape(answer)),
+        (None, None) => String::new(),
+    };
+    let diagnostics: Vec<String> = phase
+        .diagnostics
+        .iter()
+        .map(|(name, value)| format!("{}: {}", escape(name), escape(&describe(value))))
+        .collect();
+    let _ = writeln!(
+        html,
+        "<tr><td>{}</td><td>{}</td><td class=\"time\">{}</td><td>{}</td></tr>",
+        name,
+        result,
+        time(phase),
+        diagnostics.join("<br>")
+    );
+}
+
+fn write_day(html: &mut String, day: &DayReport) {
+    let report = &day.report;
+    let _ = writeln!(
+        html,
+        "<section id=\"day{0}\">\n<h2>Day {0}</h2>\n<p>Input: {1}</p>",
+        report.day,
+        escape(&report.input)
+    );
+    html.push_str("<table>\n<tr><th></th><th>answer</th><th>time</th><th>diagnostics</th></tr>\n");
+    write_phase(html, "parse", &report.parse);
+    for phase in &report.parts {
+        write_phase(html, &format!("part {}", phase.part.unwrap_or(0)), phase);
+    }
+    html.push_str("</table>\n");
+
+    for picture in &day.pictures {
+        let _ = writeln!(
+            html,
+            "<figure>\n{}<figcaption>Part {}, frame {}</figcaption>\n</figure>",
+            picture.svg, picture.part, picture.frame
+        );
+    }
+    html.push_str("</section>\n");
+}
+
+/// The whole page: a summary table of every day's answers and timings, then
+/// a section for each day with its diagnostics and pictures. Everything is
+/// inline, so the page can be opened or passed around on its own.
+pub fn render(title: &str, days: &[DayReport]) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{0}</title>\n<style>{1}</style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        escape(title),
+        STYLE
+    );
+
+    html.push_str(
+        "<table>\n<tr><th>day</th><th>part 1</th><th>part 2</th>\
+         <th>parse</th><th>part 1 time</th><th>part 2 time</th></tr>\n",
+    );
+    for day in days {
+        let report = &day.report;
+        let mut times = format!("<td class=\"time\">{}</td>", time(&report.parse));
+        for part in 1..=2 {
+            let phase = report.parts.iter().find(|p| p.part == Some(part));
+            let _ = write!(
+                times,
+                "<td class=\"time\">{}</td>",
+                phase.map_or("-".to_string(), time)
+            );
+        }
+        let _ = writeln!(
+            html,
+            "<tr><td><a href=\"#day{0}\">{0}</a></td><td>{1}</td><td>{2}</td>{3}</tr>",
+            report.day,
+            short_answer(report, 1),
+            short_answer(report, 2),
+            times
+        );
+    }
+    html.push_str("</table>\n");
+
+    for day in days {
+        write_day(&mut html, day);
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
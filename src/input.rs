@@ -0,0 +1,152 @@
+use std::{fs, io, iter::FromIterator, path::Path};
+
+use crate::{error::InputError, grid::Grid};
+
+/// Reads the whole of `reader` as text.
+pub fn read(mut reader: impl io::Read) -> Result<String, InputError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Reads the file at `path`, naming it in any error.
+pub fn read_file(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref();
+    fs::File::open(path)
+        .map_err(InputError::from)
+        .and_then(read)
+        .map_err(|e| e.in_file(path.display().to_string()))
+}
+
+/// Runs `parse_line` over every non-blank line of `input`, trimmed, and
+/// collects the results. Errors are pinned to the line they came from, with
+/// any column counted from the start of the line as it was written.
+pub fn parse_lines<T, B: FromIterator<T>>(
+    input: &str,
+    mut parse_line: impl FnMut(&str) -> Result<T, InputError>,
+) -> Result<B, InputError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+            (idx + 1, indent, line.trim())
+        })
+        .filter(|(_, _, line)| !line.is_empty())
+        .map(|(number, indent, line)| {
+            parse_line(line).map_err(|e| e.at(number, line).indented(indent))
+        })
+        .collect()
+}
+
+/// A run of lines with blank lines (or the start or end of the input) on
+/// either side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// The 1-based number of the block's first line in the whole input.
+    pub first_line: usize,
+    /// The lines as they were written, leading spaces and all.
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Block<'a> {
+    /// The lines joined back together, for parsers that work on text.
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Runs `parse` over the block. It should number lines from 1 at the top
+    /// of the block; errors come back numbered from the top of the input, and
+    /// any without a line are pinned to the block's first one.
+    pub fn parse<T>(
+        &self,
+        parse: impl FnOnce(&Block<'a>) -> Result<T, InputError>,
+    ) -> Result<T, InputError> {
+        parse(self).map_err(|e| {
+            e.shifted(self.first_line - 1)
+                .at(self.first_line, self.lines[0])
+        })
+    }
+}
+
+/// Splits `input` into its blocks of lines. Any number of blank lines count
+/// as a single separator.
+pub fn blocks(input: &str) -> Vec<Block<'_>> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut in_block = false;
+    for (idx, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            in_block = false;
+        } else if in_block {
+            blocks.last_mut().unwrap().lines.push(line);
+        } else {
+            in_block = true;
+            blocks.push(Block {
+                first_line: idx + 1,
+                lines: vec![line],
+            });
+        }
+    }
+    blocks
+}
+
+/// Runs `parse_block` over every block of `input` and collects the results,
+/// with errors numbered as described for `Block::parse`.
+pub fn parse_blocks<T, B: FromIterator<T>>(
+    input: &str,
+    mut parse_block: impl FnMut(&Block) -> Result<T, InputError>,
+) -> Result<B, InputError> {
+    blocks(input)
+        .iter()
+        .map(|block| block.parse(&mut parse_block))
+        .collect()
+}
+
+/// Splits an input made of a header and a body, such as a map followed by
+/// directions for getting around it, at the blank line between them.
+pub fn sections(input: &str) -> Result<(Block<'_>, Block<'_>), InputError> {
+    let mut blocks = blocks(input);
+    if blocks.len() != 2 {
+        return Err(InputError::malformed(format!(
+            "expected two sections separated by a blank line, not {}",
+            blocks.len()
+        )));
+    }
+    let body = blocks.pop().unwrap();
+    Ok((blocks.pop().unwrap(), body))
+}
+
+/// Reads `reader` and parses each of its lines as `parse_lines` does.
+pub fn read_lines<T, B: FromIterator<T>>(
+    reader: impl io::Read,
+    parse_line: impl FnMut(&str) -> Result<T, InputError>,
+) -> Result<B, InputError> {
+    parse_lines(&read(reader)?, parse_line)
+}
+
+/// Reads `reader` and parses each of its blocks as `parse_blocks` does.
+pub fn read_blocks<T, B: FromIterator<T>>(
+    reader: impl io::Read,
+    parse_block: impl FnMut(&Block) -> Result<T, InputError>,
+) -> Result<B, InputError> {
+    parse_blocks(&read(reader)?, parse_block)
+}
+
+/// Reads a character map from `reader`, as `Grid::parse` does.
+pub fn read_grid<T>(
+    reader: impl io::Read,
+    f: impl FnMut(char) -> Result<T, InputError>,
+) -> Result<Grid<T>, InputError> {
+    Grid::parse(&read(reader)?, f)
+}
+
+/// Reads a header and a body from `reader`, passing each to its own parser.
+pub fn read_sections<H, B>(
+    reader: impl io::Read,
+    parse_header: impl FnOnce(&Block) -> Result<H, InputError>,
+    parse_body: impl FnOnce(&Block) -> Result<B, InputError>,
+) -> Result<(H, B), InputError> {
+    let input = read(reader)?;
+    let (header, body) = sections(&input)?;
+    Ok((header.parse(parse_header)?, body.parse(parse_body)?))
+}
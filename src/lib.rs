@@ -0,0 +1,26 @@
+pub mod answers;
+pub mod bench;
+pub mod checked;
+pub mod cycle;
+pub mod days;
+pub mod diagnostics;
+pub mod differential;
+pub mod error;
+pub mod export;
+pub mod fuzz;
+pub mod geometry;
+pub mod grid;
+pub mod html;
+pub mod input;
+pub mod logging;
+pub mod params;
+pub mod pattern;
+pub mod pathfinding;
+pub mod profile;
+pub mod report;
+pub mod run_all;
+pub mod solution;
+pub mod verify;
+pub mod viewer;
+#[cfg(target_os = "linux")]
+pub mod watch;
@@ -0,0 +1,90 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much to say on stderr besides the answers, from `-q` up to `-vv`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Nothing but errors.
+    Quiet,
+    /// What each day found along the way, such as its diagnostics.
+    Normal,
+    /// How long each step took, and other detail about how the run went.
+    Verbose,
+    /// Progress from inside the slow loops.
+    Trace,
+}
+
+impl Level {
+    /// The level asked for by one of the command line's `-q`, `-v` or `-vv`.
+    pub fn from_flag(flag: &str) -> Option<Level> {
+        match flag {
+            "-q" => Some(Level::Quiet),
+            "-v" => Some(Level::Verbose),
+            "-vv" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Normal as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether messages at `level` are being shown. Cheap enough to check inside
+/// hot loops.
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Prints to stderr if messages at `level` are being shown. The arguments
+/// aren't formatted otherwise.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::logging::enabled($level) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log!($crate::logging::Level::Normal, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => { $crate::log!($crate::logging::Level::Verbose, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => { $crate::log!($crate::logging::Level::Trace, $($arg)*) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_from_flags() {
+        assert_eq!(Level::from_flag("-q"), Some(Level::Quiet));
+        assert_eq!(Level::from_flag("-v"), Some(Level::Verbose));
+        assert_eq!(Level::from_flag("-vv"), Some(Level::Trace));
+        assert_eq!(Level::from_flag("-vvv"), None);
+        assert_eq!(Level::from_flag("--verbose"), None);
+    }
+
+    #[test]
+    fn each_level_shows_the_ones_below_it() {
+        set_level(Level::Verbose);
+        let shown: Vec<bool> = [Level::Quiet, Level::Normal, Level::Verbose, Level::Trace]
+            .iter()
+            .map(|&level| enabled(level))
+            .collect();
+        set_level(Level::Normal);
+        assert_eq!(shown, [true, true, true, false]);
+        assert!(!enabled(Level::Verbose));
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    error, fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{profile::DATA_DIR, solution::Solution};
+
+/// Where the checked-in parameters for each input live.
+pub const PARAMS_PATH: &str = "./data/params.json";
+
+/// Puzzle constants by name, such as day 15's `max_search`. Each solver
+/// carries its own, with defaults that suit the real inputs.
+pub type Params = Map<String, Value>;
+
+/// Parameters for one day, for one input file or for all of them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamsEntry {
+    pub day: usize,
+    /// Input file name, relative to the `data` directory. Left out for
+    /// parameters that apply to every input for the day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    pub params: Params,
+}
+
+/// Reads a params file. A missing file is the same as an empty one, as every
+/// parameter has a default.
+pub fn load(path: &str) -> Result<Vec<ParamsEntry>, Box<dyn error::Error>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Couldn't read params file {}: {}", path, e).into()),
+    };
+    let entries: Vec<ParamsEntry> = serde_json::from_str(&contents)
+        .map_err(|e| format!("Couldn't parse params file {}: {}", path, e))?;
+    Ok(entries)
+}
+
+/// `path` without any `.` components, so that `./data/day1.txt` and
+/// `data/day1.txt` compare equal.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| *c != Component::CurDir)
+        .collect()
+}
+
+/// `input_path` relative to the data directory, as entries name their inputs:
+/// `./data/alice/day1.txt` is `alice/day1.txt`. Paths that are already
+/// relative to it are left as they are.
+fn relative_to_data_dir(input_path: &str) -> PathBuf {
+    let path = normalize(Path::new(input_path));
+    match path.strip_prefix(normalize(Path::new(DATA_DIR))) {
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => path,
+    }
+}
+
+/// The parameters for running `day` on the input at `input_path`: the
+/// entries for the whole day, then the entries for that input file, then
+/// `overrides`, each replacing values from the ones before.
+pub fn lookup(entries: &[ParamsEntry], day: usize, input_path: &str, overrides: &Params) -> Params {
+    let input = relative_to_data_dir(input_path);
+    let day_entries = entries.iter().filter(|e| e.day == day);
+    let whole_day = day_entries.clone().filter(|e| e.input.is_none());
+    let this_input = day_entries.filter(|e| {
+        e.input
+            .as_deref()
+            .is_some_and(|name| normalize(Path::new(name)) == input)
+    });
+
+    let mut params = Params::new();
+    for entry in whole_day.chain(this_input) {
+        params.extend(entry.params.clone());
+    }
+    params.extend(overrides.clone());
+    params
+}
+
+/// Parses a `name=value` override from the command line. The value is read as
+/// JSON if it can be, and as a plain string otherwise.
+pub fn parse_override(arg: &str) -> Result<(String, Value), Box<dyn error::Error>> {
+    let (name, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("Expected name=value, not {}", arg))?;
+    let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+    Ok((name.to_string(), value))
+}
+
+/// A copy of `solver` with `params` in place of its own values. Every name in
+/// `params` has to be one of the solver's parameters.
+pub fn configure<S: Solution>(solver: &S, params: &Params) -> Result<S, Box<dyn error::Error>> {
+    let mut value = serde_json::to_value(solver)?;
+    let known: Vec<String> = value
+        .as_object()
+        .map_or(Vec::new(), |fields| fields.keys().cloned().collect());
+
+    for (name, new_value) in params {
+        match value.get_mut(name) {
+            Some(field) => *field = new_value.clone(),
+            None if known.is_empty() => {
+                return Err(format!("Day {} has no parameters to set {} on", S::DAY, name).into())
+            }
+            None => {
+                return Err(format!(
+                    "Day {} has no parameter {}; it has {}",
+                    S::DAY,
+                    name,
+                    known.join(", ")
+                )
+                .into())
+            }
+        }
+    }
+
+    serde_json::from_value(value)
+        .map_err(|e| format!("Bad parameter for day {}: {}", S::DAY, e).into())
+}
+
+/// A description of `params` for messages, e.g. "max_search=20, target_row=10".
+pub fn describe(params: &Params) -> String {
+    params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,306 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{geometry::Coord, trace};
+
+/// A route found by one of the searches: every state visited from a start to
+/// the goal, both inclusive, and the total cost of the steps between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub states: Vec<S>,
+    pub cost: C,
+}
+
+impl<S, C> Path<S, C> {
+    pub fn start(&self) -> &S {
+        self.states.first().unwrap()
+    }
+
+    pub fn goal(&self) -> &S {
+        self.states.last().unwrap()
+    }
+}
+
+/// A discovered state, linked back to the one it was reached from.
+struct Node<S, C> {
+    state: S,
+    parent: Option<usize>,
+    cost: C,
+}
+
+fn reconstruct<S: Clone, C: Copy>(nodes: &[Node<S, C>], goal: usize) -> Path<S, C> {
+    let mut states = Vec::new();
+    let mut idx = Some(goal);
+    while let Some(i) = idx {
+        states.push(nodes[i].state.clone());
+        idx = nodes[i].parent;
+    }
+    states.reverse();
+
+    Path {
+        states,
+        cost: nodes[goal].cost,
+    }
+}
+
+/// Visits states breadth first from `starts` until one satisfies `is_goal`,
+/// returning every state discovered along with the goal's index, if found.
+fn breadth_first<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> (Vec<Node<S, usize>>, Option<usize>)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen: HashSet<S> = HashSet::new();
+    let mut nodes: Vec<Node<S, usize>> = Vec::new();
+    for start in starts {
+        if seen.insert(start.clone()) {
+            nodes.push(Node {
+                state: start,
+                parent: None,
+                cost: 0,
+            });
+        }
+    }
+
+    // States are discovered in the order they should be visited, so the node
+    // list doubles as the queue.
+    let mut next = 0;
+    let mut depth = 0;
+    while next < nodes.len() {
+        if nodes[next].cost > depth {
+            depth = nodes[next].cost;
+            trace!("bfs: reached depth {}, {} states seen", depth, nodes.len());
+        }
+        if is_goal(&nodes[next].state) {
+            return (nodes, Some(next));
+        }
+
+        let cost = nodes[next].cost + 1;
+        for state in successors(&nodes[next].state) {
+            if seen.insert(state.clone()) {
+                nodes.push(Node {
+                    state,
+                    parent: Some(next),
+                    cost,
+                });
+            }
+        }
+        next += 1;
+    }
+
+    (nodes, None)
+}
+
+/// Finds the path with the fewest steps from any of `starts` to a state
+/// satisfying `is_goal`, where every step costs one.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let (nodes, goal) = breadth_first(starts, successors, is_goal);
+    goal.map(|goal| reconstruct(&nodes, goal))
+}
+
+/// The fewest steps from any of `starts` to every state that can be reached
+/// from them, where every step costs one.
+pub fn bfs_distances<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let (nodes, _) = breadth_first(starts, successors, |_| false);
+    nodes
+        .into_iter()
+        .map(|node| (node.state, node.cost))
+        .collect()
+}
+
+/// Finds the cheapest path from any of `starts` to a state satisfying
+/// `is_goal`. `successors` gives each next state with the cost of the step
+/// to it, which must not be negative.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Coord,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::ZERO, is_goal)
+}
+
+/// Like `dijkstra`, but guided by `heuristic`, an estimate of the remaining
+/// cost to the goal. The path is only guaranteed to be the cheapest if the
+/// estimate never overshoots.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Coord,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // The node holding the cheapest known route to each state
+    let mut best: HashMap<S, usize> = HashMap::new();
+    let mut nodes: Vec<Node<S, C>> = Vec::new();
+    let mut open: BinaryHeap<Reverse<(C, usize)>> = BinaryHeap::new();
+
+    for start in starts {
+        if best.contains_key(&start) {
+            continue;
+        }
+        best.insert(start.clone(), nodes.len());
+        open.push(Reverse((heuristic(&start), nodes.len())));
+        nodes.push(Node {
+            state: start,
+            parent: None,
+            cost: C::ZERO,
+        });
+    }
+
+    while let Some(Reverse((_, idx))) = open.pop() {
+        let state = nodes[idx].state.clone();
+        if best[&state] != idx {
+            // A cheaper route to this state was found after this one was
+            // queued
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(reconstruct(&nodes, idx));
+        }
+
+        let cost = nodes[idx].cost;
+        for (next_state, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            if let Some(&existing) = best.get(&next_state) {
+                if nodes[existing].cost <= next_cost {
+                    continue;
+                }
+            }
+
+            best.insert(next_state.clone(), nodes.len());
+            open.push(Reverse((next_cost + heuristic(&next_state), nodes.len())));
+            nodes.push(Node {
+                state: next_state,
+                parent: Some(idx),
+                cost: next_cost,
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small directed graph: a is joined to d both directly, by an
+    /// expensive edge, and through b and c, by cheap ones. e leads nowhere
+    /// and can't be reached.
+    const EDGES: &[(char, char, u32)] = &[
+        ('a', 'b', 1),
+        ('b', 'c', 1),
+        ('c', 'd', 1),
+        ('a', 'd', 10),
+        ('d', 'a', 1),
+        ('f', 'c', 1),
+    ];
+
+    fn weighted(from: &char) -> Vec<(char, u32)> {
+        EDGES
+            .iter()
+            .filter(|&&(a, _, _)| a == *from)
+            .map(|&(_, b, cost)| (b, cost))
+            .collect()
+    }
+
+    fn unweighted(from: &char) -> Vec<char> {
+        weighted(from).into_iter().map(|(b, _)| b).collect()
+    }
+
+    #[test]
+    fn fewest_steps_or_cheapest() {
+        let path = bfs(Some('a'), unweighted, |&s| s == 'd').unwrap();
+        assert_eq!(path.states, vec!['a', 'd']);
+        assert_eq!(path.cost, 1);
+
+        let path = dijkstra(Some('a'), weighted, |&s| s == 'd').unwrap();
+        assert_eq!(path.states, vec!['a', 'b', 'c', 'd']);
+        assert_eq!(path.cost, 3);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        assert_eq!(bfs(Some('a'), unweighted, |&s| s == 'e'), None);
+        assert_eq!(dijkstra(Some('a'), weighted, |&s| s == 'e'), None);
+        assert_eq!(astar(Some('a'), weighted, |_| 0, |&s| s == 'f'), None);
+    }
+
+    #[test]
+    fn nearest_of_several_starts() {
+        let path = bfs(vec!['a', 'f'], unweighted, |&s| s == 'c').unwrap();
+        assert_eq!((path.start(), path.goal(), path.cost), (&'f', &'c', 1));
+
+        let path = dijkstra(vec!['a', 'f'], weighted, |&s| s == 'd').unwrap();
+        assert_eq!(path.states, vec!['f', 'c', 'd']);
+        assert_eq!(path.cost, 2);
+
+        // Starting at the goal is a path of no steps
+        let path = dijkstra(vec!['b', 'd'], weighted, |&s| s == 'd').unwrap();
+        assert_eq!(path.states, vec!['d']);
+        assert_eq!(path.cost, 0);
+    }
+
+    #[test]
+    fn guided_by_a_heuristic() {
+        // Walking on a grid from (0, 0) to (5, 3), with the columns at x = 3
+        // costing more to step into the higher they are
+        let successors = |&(x, y): &(i32, i32)| {
+            let steps = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)];
+            steps
+                .iter()
+                .filter(|&&(x, y)| (0..=5).contains(&x) && (0..=5).contains(&y))
+                .map(|&(x, y)| ((x, y), if x == 3 { 1 + y } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let goal = (5, 3);
+        let distance = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+
+        let guided = astar(Some((0, 0)), successors, distance, |&s| s == goal).unwrap();
+        let unguided = dijkstra(Some((0, 0)), successors, |&s| s == goal).unwrap();
+        assert_eq!(guided.cost, 8);
+        assert_eq!(guided.cost, unguided.cost);
+        assert_eq!(guided.states.len(), 9);
+        assert!(guided.states.contains(&(3, 0)));
+    }
+
+    #[test]
+    fn distances_to_everything_reachable() {
+        let distances = bfs_distances(Some('b'), unweighted);
+        let expected: HashMap<char, usize> = vec![('b', 0), ('c', 1), ('d', 2), ('a', 3)]
+            .into_iter()
+            .collect();
+        assert_eq!(distances, expected);
+    }
+}
@@ -0,0 +1,112 @@
+use std::{fmt::Display, str::FromStr};
+
+use regex::Regex;
+
+use crate::error::InputError;
+
+/// A regular expression for a whole line, as used by `line_pattern!`.
+pub struct Pattern {
+    source: &'static str,
+    regex: Regex,
+}
+
+impl Pattern {
+    /// Compiles `source`, which should have a capture group for each of the
+    /// `fields`. Both are fixed in the code, so mistakes in them panic.
+    pub fn new(source: &'static str, fields: usize) -> Pattern {
+        let regex = Regex::new(&format!("^(?:{})$", source))
+            .unwrap_or_else(|e| panic!("bad pattern {:?}: {}", source, e));
+        assert_eq!(
+            regex.captures_len() - 1,
+            fields,
+            "pattern {:?} needs one group for each field",
+            source
+        );
+        Pattern { source, regex }
+    }
+
+    /// The text of each group in `line`, or an error showing the pattern if
+    /// the line doesn't match it. Groups that took no part in the match come
+    /// back empty.
+    pub fn captures<'a>(&self, line: &'a str) -> Result<Vec<&'a str>, InputError> {
+        let cap = self.regex.captures(line).ok_or_else(|| {
+            InputError::malformed(format!("expected a line matching /{}/", self.source))
+        })?;
+        Ok(cap
+            .iter()
+            .skip(1)
+            .map(|group| group.map_or("", |group| group.as_str()))
+            .collect())
+    }
+}
+
+/// Converts the text captured for the field `name`.
+pub fn field<T: FromStr>(name: &str, text: &str) -> Result<T, InputError>
+where
+    T::Err: Display,
+{
+    text.parse()
+        .map_err(|e| InputError::malformed(format!("bad {} {:?} ({})", name, text, e)))
+}
+
+/// A comma separated list, for capturing as one field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct List<T>(pub Vec<T>);
+
+impl<T: FromStr> FromStr for List<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|item| item.trim().parse())
+            .collect::<Result<_, _>>()
+            .map(List)
+    }
+}
+
+/// Declares a struct that's read from a line of input matching a regular
+/// expression, with one capture group per field, in order. Each field is
+/// converted with its type's `FromStr`, and the struct gets a `parse_line`
+/// function that reads one from a line:
+///
+/// ```text
+/// line_pattern! {
+///     struct Move = r"move (\d+) from (\d+) to (\d+)" {
+///         quantity: usize,
+///         src: usize,
+///         dst: usize,
+///     }
+/// }
+/// ```
+///
+/// The pattern has to match the whole line. It's compiled the first time a
+/// line is parsed.
+#[macro_export]
+macro_rules! line_pattern {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident = $pattern:literal {
+            $($(#[$field_meta:meta])* $field_vis:vis $field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($(#[$field_meta])* $field_vis $field: $ty),*
+        }
+
+        impl $name {
+            $vis fn parse_line(line: &str) -> Result<$name, $crate::error::InputError> {
+                static PATTERN: ::std::sync::OnceLock<$crate::pattern::Pattern> =
+                    ::std::sync::OnceLock::new();
+                let fields = [$(stringify!($field)),*];
+                let captures = PATTERN
+                    .get_or_init(|| $crate::pattern::Pattern::new($pattern, fields.len()))
+                    .captures(line)?;
+                let mut captures = captures.into_iter();
+                Ok($name {
+                    $($field: $crate::pattern::field(stringify!($field), captures.next().unwrap())?),*
+                })
+            }
+        }
+    };
+}
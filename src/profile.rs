@@ -0,0 +1,131 @@
+use std::{
+    env, error, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::answers::{self, Expected};
+
+/// Where the puzzle inputs live. The default profile's are directly inside
+/// it, along with the worked examples everyone shares.
+pub const DATA_DIR: &str = "./data";
+
+/// The environment variable naming the profile to use, when none is given on
+/// the command line.
+pub const PROFILE_VAR: &str = "AOC_PROFILE";
+
+/// One person's puzzle inputs and the answers expected for them. The default
+/// profile is `data/` itself; a named one is a directory inside it laid out
+/// the same way, as `data/<name>/dayN.txt` and `data/<name>/answers.json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    root: PathBuf,
+    name: Option<String>,
+}
+
+impl Default for Profile {
+    fn default() -> Profile {
+        Profile {
+            root: PathBuf::from(DATA_DIR),
+            name: None,
+        }
+    }
+}
+
+impl Profile {
+    pub fn named(name: &str) -> Result<Profile, Box<dyn error::Error>> {
+        Profile::named_in(DATA_DIR, name)
+    }
+
+    /// The profile called `name` in `root` rather than the data directory.
+    pub fn named_in(root: impl AsRef<Path>, name: &str) -> Result<Profile, Box<dyn error::Error>> {
+        if name.is_empty() || name.starts_with('.') || name.contains(&['/', '\\'][..]) {
+            return Err(format!("Bad profile name {:?}", name).into());
+        }
+        let profile = Profile {
+            root: root.as_ref().to_path_buf(),
+            name: Some(name.to_string()),
+        };
+        if !profile.dir().is_dir() {
+            return Err(format!(
+                "No profile {} (expected a directory at {})",
+                name,
+                profile.dir().display()
+            )
+            .into());
+        }
+        Ok(profile)
+    }
+
+    /// The profile named by `AOC_PROFILE`, or the default one if that isn't
+    /// set.
+    pub fn from_env() -> Result<Profile, Box<dyn error::Error>> {
+        match env::var(PROFILE_VAR) {
+            Ok(name) if !name.is_empty() => Profile::named(&name),
+            _ => Ok(Profile::default()),
+        }
+    }
+
+    pub fn all() -> Result<Vec<Profile>, Box<dyn error::Error>> {
+        Profile::all_in(DATA_DIR)
+    }
+
+    /// The default profile in `root`, then every directory in it that has an
+    /// answers file, by name.
+    pub fn all_in(root: impl AsRef<Path>) -> Result<Vec<Profile>, Box<dyn error::Error>> {
+        let root = root.as_ref();
+        let mut names: Vec<String> = Vec::new();
+        for entry in fs::read_dir(root)? {
+            let path = entry?.path();
+            if path.join("answers.json").is_file() {
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort();
+
+        let default = Profile {
+            root: root.to_path_buf(),
+            name: None,
+        };
+        let named = names.iter().map(|name| Profile::named_in(root, name));
+        std::iter::once(Ok(default)).chain(named).collect()
+    }
+
+    /// What to call the profile in messages.
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("default")
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        match &self.name {
+            Some(name) => self.root.join(name),
+            None => self.root.clone(),
+        }
+    }
+
+    pub fn input_path(&self, day: usize) -> String {
+        self.dir()
+            .join(format!("day{}.txt", day))
+            .display()
+            .to_string()
+    }
+
+    pub fn answers_path(&self) -> String {
+        self.dir().join("answers.json").display().to_string()
+    }
+
+    /// The profile's expected answers, with each input named relative to the
+    /// data directory (e.g. `alice/day1.txt`) so that they can be told apart
+    /// from other profiles' in a table.
+    pub fn load_answers(&self) -> Result<Vec<Expected>, Box<dyn error::Error>> {
+        let mut expected = answers::load(&self.answers_path())?;
+        for e in &mut expected {
+            e.dir = self.root.clone();
+            if let Some(name) = &self.name {
+                e.input = format!("{}/{}", name, e.input);
+            }
+        }
+        Ok(expected)
+    }
+}
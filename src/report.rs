@@ -0,0 +1,169 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    diagnostics::{self, Diagnostics},
+    solution::AnySolution,
+};
+
+/// Everything that happened while solving one day once, in the shape printed
+/// by `--format json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolveReport {
+    pub day: usize,
+    pub input: String,
+    pub parse: PhaseReport,
+    pub parts: Vec<PhaseReport>,
+}
+
+impl SolveReport {
+    /// Whether parsing and every part that was run succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.parse.error.is_none() && self.parts.iter().all(|p| p.error.is_none())
+    }
+}
+
+/// How parsing or one of the parts went. Parsing has no `part` or `answer`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub elapsed_ns: u64,
+    #[serde(default, skip_serializing_if = "Diagnostics::is_empty")]
+    pub diagnostics: Diagnostics,
+}
+
+/// Runs `f`, timing it and collecting whatever it notes.
+fn observed<T>(f: impl FnOnce() -> T) -> (T, Duration, Diagnostics) {
+    let start = Instant::now();
+    let (result, diagnostics) = diagnostics::collect(f);
+    (result, start.elapsed(), diagnostics)
+}
+
+/// Parses `input` and runs each of `parts` on it, keeping going after a part
+/// fails so that the report covers every part asked for.
+pub fn solve(
+    solution: &dyn AnySolution,
+    input_name: &str,
+    input: &str,
+    parts: &[usize],
+) -> SolveReport {
+    let (parsed, elapsed, diagnostics) = observed(|| solution.parse(input));
+    let (parsed, error) = match parsed {
+        Ok(parsed) => (Some(parsed), None),
+        Err(e) => (None, Some(e.in_file(input_name).to_string())),
+    };
+    let parse = PhaseReport {
+        part: None,
+        answer: None,
+        error,
+        elapsed_ns: elapsed.as_nanos() as u64,
+        diagnostics,
+    };
+
+    let parts = match parsed {
+        Some(parsed) => parts
+            .iter()
+            .map(|&part| {
+                let (answer, elapsed, diagnostics) = observed(|| solution.part(part, &*parsed));
+                let (answer, error) = match answer {
+                    Ok(answer) => (Some(answer), None),
+                    Err(e) => (None, Some(e.to_string())),
+                };
+                PhaseReport {
+                    part: Some(part),
+                    answer,
+                    error,
+                    elapsed_ns: elapsed.as_nanos() as u64,
+                    diagnostics,
+                }
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    SolveReport {
+        day: solution.day(),
+        input: input_name.to_string(),
+        parse,
+        parts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::days;
+
+    fn sample(day: usize) -> String {
+        std::fs::read_to_string(format!("./data/day{}-sample.txt", day)).unwrap()
+    }
+
+    #[test]
+    fn every_part_asked_for() {
+        let report = solve(days::get(1).unwrap(), "sample", &sample(1), &[2, 1]);
+        assert!(report.is_ok());
+        assert_eq!((report.day, report.input.as_str()), (1, "sample"));
+        let answers: Vec<_> = report
+            .parts
+            .iter()
+            .map(|p| (p.part, p.answer.as_deref()))
+            .collect();
+        assert_eq!(
+            answers,
+            [(Some(2), Some("45000")), (Some(1), Some("24000"))]
+        );
+    }
+
+    #[test]
+    fn failures_are_reported_not_returned() {
+        let report = solve(days::get(1).unwrap(), "sample", &sample(1), &[1, 3, 2]);
+        assert!(!report.is_ok());
+        assert!(report.parts[1].error.is_some());
+        assert_eq!(report.parts[2].answer.as_deref(), Some("45000"));
+
+        // Nothing is run on input that doesn't parse
+        let report = solve(days::get(1).unwrap(), "bad.txt", "x\n", &[1, 2]);
+        assert!(!report.is_ok());
+        assert!(report
+            .parse
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("bad.txt:1:"));
+        assert!(report.parts.is_empty());
+    }
+
+    #[test]
+    fn diagnostics_go_with_their_phase() {
+        let report = solve(days::get(17).unwrap(), "sample", &sample(17), &[2]);
+        assert!(!report.parse.diagnostics.contains_key("cycle"));
+        assert_eq!(
+            report.parts[0].diagnostics["cycle"],
+            json!({ "start": 28, "period": 35, "delta": 53 })
+        );
+    }
+
+    #[test]
+    fn empty_fields_are_left_out_of_json() {
+        let mut report = solve(days::get(1).unwrap(), "sample", &sample(1), &[1]);
+        report.parse.elapsed_ns = 5;
+        report.parts[0].elapsed_ns = 7;
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "day": 1,
+                "input": "sample",
+                "parse": { "elapsed_ns": 5 },
+                "parts": [{ "part": 1, "answer": "24000", "elapsed_ns": 7 }],
+            })
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    panic,
+    sync::mpsc::{self, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{
+    bench::format_duration, input, profile::Profile, solution::AnySolution, verbose,
+    verify::panic_message,
+};
+
+const PARTS: [usize; 2] = [1, 2];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "details", rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Error(String),
+    Panic(String),
+    /// The day was still running when its time ran out.
+    Timeout,
+}
+
+impl Status {
+    pub fn is_ok(&self) -> bool {
+        *self == Status::Ok
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Error(_) => "ERROR",
+            Status::Panic(_) => "PANIC",
+            Status::Timeout => "TIMEOUT",
+        }
+    }
+}
+
+/// How one part of one day went.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartRun {
+    pub day: usize,
+    pub part: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    /// How long the part took, not counting parsing. For a timeout, how long
+    /// the day had been running when it was given up on.
+    pub elapsed_ns: u64,
+    #[serde(flatten)]
+    pub status: Status,
+}
+
+/// The whole of an `--all` run, in the shape printed by `--format json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunAllReport {
+    pub ok: usize,
+    pub failed: usize,
+    pub elapsed_ns: u64,
+    pub runs: Vec<PartRun>,
+}
+
+enum Event {
+    Started(usize, Instant),
+    Part(PartRun),
+    /// Sent even after the day has timed out, so that the runner knows the
+    /// worker is free again.
+    Finished(usize),
+}
+
+/// Runs `f`, turning both errors and panics into a failed `Status`.
+fn guarded<T, E: ToString>(f: impl FnOnce() -> Result<T, E>) -> Result<T, Status> {
+    match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(Status::Error(e.to_string())),
+        Err(payload) => Err(Status::Panic(panic_message(payload))),
+    }
+}
+
+/// Solves one day's input from `profile`, sending each part's result as soon as it's
+/// known. The receiver may have stopped listening, which is fine.
+fn run_day(solution: &dyn AnySolution, profile: &Profile, events: &mpsc::Sender<Event>) {
+    let day = solution.day();
+    let _ = events.send(Event::Started(day, Instant::now()));
+    verbose!("Day {} started", day);
+
+    let path = profile.input_path(day);
+    let parsed = guarded(|| {
+        let input = input::read_file(&path)?;
+        solution.parse(&input).map_err(|e| e.in_file(&path))
+    });
+
+    for part in PARTS {
+        let start = Instant::now();
+        let (answer, status) = match &parsed {
+            Ok(parsed) => match guarded(|| solution.part(part, &**parsed)) {
+                Ok(answer) => (Some(answer), Status::Ok),
+                Err(status) => (None, status),
+            },
+            Err(status) => (None, status.clone()),
+        };
+        let _ = events.send(Event::Part(PartRun {
+            day,
+            part,
+            answer,
+            elapsed_ns: start.elapsed().as_nanos() as u64,
+            status,
+        }));
+    }
+
+    let _ = events.send(Event::Finished(day));
+}
+
+/// Runs both parts of every day in `solutions` on its input from `profile`,
+/// concurrently on the rayon pool, giving up on any day still running `timeout` after it started. A day that
+/// panics or times out doesn't hold up the others.
+///
+/// A day that times out can't be stopped, so it keeps its worker busy. If every
+/// worker ends up stuck like that, the days that never got to start are
+/// reported as errors rather than waited on forever.
+pub fn run(
+    solutions: Vec<Box<dyn AnySolution>>,
+    profile: &Profile,
+    timeout: Duration,
+) -> RunAllReport {
+    // Keep the panic hook quiet, as the panics are reported in the table
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let start = Instant::now();
+    let days: Vec<usize> = solutions.iter().map(|s| s.day()).collect();
+    let (sender, events) = mpsc::channel();
+    for solution in solutions {
+        let sender = sender.clone();
+        let profile = profile.clone();
+        rayon::spawn(move || run_day(&*solution, &profile, &sender));
+    }
+    drop(sender);
+
+    let mut runs: BTreeMap<(usize, usize), PartRun> = BTreeMap::new();
+    let mut started: HashMap<usize, Instant> = HashMap::new();
+    let mut finished: HashSet<usize> = HashSet::new();
+    let is_done = |runs: &BTreeMap<_, _>, day: usize| {
+        PARTS.iter().all(|&part| runs.contains_key(&(day, part)))
+    };
+
+    while runs.len() < days.len() * PARTS.len() {
+        let deadline = started
+            .iter()
+            .filter(|&(&day, _)| !is_done(&runs, day))
+            .map(|(_, &at)| at + timeout)
+            .min();
+        let event = match deadline {
+            Some(deadline) => {
+                events.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => events.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match event {
+            Ok(Event::Started(day, at)) => {
+                started.insert(day, at);
+            }
+            // Anything from a day that has already timed out is ignored
+            Ok(Event::Part(run)) => {
+                runs.entry((run.day, run.part)).or_insert(run);
+            }
+            Ok(Event::Finished(day)) => {
+                finished.insert(day);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let now = Instant::now();
+                for (&day, &at) in &started {
+                    if now < at + timeout {
+                        continue;
+                    }
+                    for part in PARTS {
+                        runs.entry((day, part)).or_insert_with(|| PartRun {
+                            day,
+                            part,
+                            answer: None,
+                            elapsed_ns: (now - at).as_nanos() as u64,
+                            status: Status::Timeout,
+                        });
+                    }
+                }
+
+                let stuck = started
+                    .keys()
+                    .filter(|day| !finished.contains(day))
+                    .filter(|&&day| is_done(&runs, day))
+                    .count();
+                if stuck >= rayon::current_num_threads() {
+                    for &day in &days {
+                        if started.contains_key(&day) {
+                            continue;
+                        }
+                        for part in PARTS {
+                            runs.insert(
+                                (day, part),
+                                PartRun {
+                                    day,
+                                    part,
+                                    answer: None,
+                                    elapsed_ns: 0,
+                                    status: Status::Error(
+                                        "never started: every worker is stuck on a day that \
+                                         timed out"
+                                            .to_string(),
+                                    ),
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    panic::set_hook(hook);
+    let runs: Vec<PartRun> = runs.into_values().collect();
+    let failed = runs.iter().filter(|r| !r.status.is_ok()).count();
+    RunAllReport {
+        ok: runs.len() - failed,
+        failed,
+        elapsed_ns: start.elapsed().as_nanos() as u64,
+        runs,
+    }
+}
+
+pub fn print_table(report: &RunAllReport) {
+    // Answers that span several lines (the CRT screens) go below their row
+    let inline_answer = |run: &PartRun| match &run.answer {
+        Some(answer) if !answer.contains('\n') => answer.clone(),
+        Some(_) => "(below)".to_string(),
+        None => "-".to_string(),
+    };
+    let answer_width = report
+        .runs
+        .iter()
+        .map(|r| inline_answer(r).chars().count())
+        .chain(std::iter::once("answer".len()))
+        .max()
+        .unwrap();
+
+    println!(
+        "{:>3}  {:>4}  {:<w$}  {:>10}  status",
+        "day",
+        "part",
+        "answer",
+        "time",
+        w = answer_width
+    );
+    for run in &report.runs {
+        println!(
+            "{:>3}  {:>4}  {:<w$}  {:>10}  {}",
+            run.day,
+            run.part,
+            inline_answer(run),
+            format_duration(Duration::from_nanos(run.elapsed_ns)),
+            run.status.label(),
+            w = answer_width
+        );
+        match (&run.answer, &run.status) {
+            (Some(answer), _) if answer.contains('\n') => {
+                for line in answer.lines() {
+                    println!("    {}", line);
+                }
+            }
+            (_, Status::Error(msg)) | (_, Status::Panic(msg)) => println!("    {}", msg),
+            _ => (),
+        }
+    }
+
+    println!();
+    println!(
+        "{} ok, {} failed in {}",
+        report.ok,
+        report.failed,
+        format_duration(Duration::from_nanos(report.elapsed_ns))
+    );
+}
@@ -0,0 +1,169 @@
+use std::{any::Any, error, fmt::Display};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    error::InputError,
+    export::{self, Image},
+    input,
+    params::{self, Params},
+    profile::{Profile, DATA_DIR},
+    viewer::Simulation,
+};
+
+/// A single day's puzzle, split into a parsing stage and the two parts that
+/// share its output. Any puzzle constants that differ between inputs are
+/// fields of the solver, which is serializable so that they can be set from
+/// the params file (see `params`).
+pub trait Solution: Serialize + DeserializeOwned {
+    const DAY: usize;
+
+    type Parsed: Any + Send;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, InputError>;
+    fn part1(&self, parsed: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>>;
+    fn part2(&self, parsed: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>>;
+
+    /// `part` run one step at a time, for the viewer. Only the days that
+    /// simulate something on a map have one.
+    fn simulation(&self, _parsed: &Self::Parsed, _part: usize) -> Option<Box<dyn Simulation>> {
+        None
+    }
+
+    /// Still pictures of `part` for exporting, for the days that have
+    /// something to show but no simulation to animate.
+    fn pictures(&self, _parsed: &Self::Parsed, _part: usize) -> Option<Vec<Image>> {
+        None
+    }
+}
+
+/// Object-safe view of a `Solution`, with the parsed input type-erased and the
+/// answers rendered as strings. This is what the runners dispatch on.
+pub trait AnySolution: Send + Sync {
+    fn day(&self) -> usize;
+
+    /// A copy of this solver with `params` in place of its own values.
+    fn configured(&self, params: &Params) -> Result<Box<dyn AnySolution>, Box<dyn error::Error>>;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send>, InputError>;
+    fn part1(&self, parsed: &(dyn Any + Send)) -> Result<String, Box<dyn error::Error>>;
+    fn part2(&self, parsed: &(dyn Any + Send)) -> Result<String, Box<dyn error::Error>>;
+
+    fn part(&self, part: usize, parsed: &(dyn Any + Send)) -> Result<String, Box<dyn error::Error>> {
+        match part {
+            1 => self.part1(parsed),
+            2 => self.part2(parsed),
+            _ => Err(format!("Day {} has no part {}", self.day(), part).into()),
+        }
+    }
+
+    fn simulation(
+        &self,
+        parsed: &(dyn Any + Send),
+        part: usize,
+    ) -> Result<Box<dyn Simulation>, Box<dyn error::Error>>;
+
+    /// The pictures to export for `part`: the day's own, or else up to
+    /// `steps` steps of its simulation.
+    fn frames(
+        &self,
+        parsed: &(dyn Any + Send),
+        part: usize,
+        steps: usize,
+    ) -> Result<Box<dyn Iterator<Item = Image>>, Box<dyn error::Error>>;
+}
+
+impl<S: Solution + Send + Sync + 'static> AnySolution for S {
+    fn day(&self) -> usize {
+        S::DAY
+    }
+
+    fn configured(&self, params: &Params) -> Result<Box<dyn AnySolution>, Box<dyn error::Error>> {
+        Ok(Box::new(params::configure(self, params)?))
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send>, InputError> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn part1(&self, parsed: &(dyn Any + Send)) -> Result<String, Box<dyn error::Error>> {
+        Ok(Solution::part1(self, downcast::<S>(parsed)?)?.to_string())
+    }
+
+    fn part2(&self, parsed: &(dyn Any + Send)) -> Result<String, Box<dyn error::Error>> {
+        Ok(Solution::part2(self, downcast::<S>(parsed)?)?.to_string())
+    }
+
+    fn simulation(
+        &self,
+        parsed: &(dyn Any + Send),
+        part: usize,
+    ) -> Result<Box<dyn Simulation>, Box<dyn error::Error>> {
+        if part != 1 && part != 2 {
+            return Err(format!("Day {} has no part {}", S::DAY, part).into());
+        }
+        Solution::simulation(self, downcast::<S>(parsed)?, part)
+            .ok_or_else(|| format!("Day {} has no simulation to view", S::DAY).into())
+    }
+
+    fn frames(
+        &self,
+        parsed: &(dyn Any + Send),
+        part: usize,
+        steps: usize,
+    ) -> Result<Box<dyn Iterator<Item = Image>>, Box<dyn error::Error>> {
+        if part != 1 && part != 2 {
+            return Err(format!("Day {} has no part {}", S::DAY, part).into());
+        }
+        let parsed = downcast::<S>(parsed)?;
+        if let Some(pictures) = Solution::pictures(self, parsed, part) {
+            return Ok(Box::new(pictures.into_iter()));
+        }
+        match Solution::simulation(self, parsed, part) {
+            Some(simulation) => Ok(Box::new(export::animate(simulation, steps))),
+            None => Err(format!("Day {} has nothing to draw", S::DAY).into()),
+        }
+    }
+}
+
+fn downcast<S: Solution>(parsed: &(dyn Any + Send)) -> Result<&S::Parsed, Box<dyn error::Error>> {
+    parsed
+        .downcast_ref::<S::Parsed>()
+        .ok_or_else(|| format!("Parsed input was not produced by day {}", S::DAY).into())
+}
+
+/// The worked example for `day`, which every profile shares.
+pub fn sample_path(day: usize) -> String {
+    format!("{}/day{}-sample.txt", DATA_DIR, day)
+}
+
+/// Parses `input` once and computes both parts.
+pub fn solve(
+    solution: &dyn AnySolution,
+    input: &str,
+) -> Result<(String, String), Box<dyn error::Error>> {
+    let parsed = solution.parse(input)?;
+    Ok((solution.part1(&*parsed)?, solution.part2(&*parsed)?))
+}
+
+/// Entry point shared by the `dayN` binaries: solves the day's input from the
+/// profile named by `AOC_PROFILE` (or the default one) and prints one answer
+/// per line.
+pub fn run_bin(solution: &dyn AnySolution) -> Result<(), Box<dyn error::Error>> {
+    let path = Profile::from_env()?.input_path(solution.day());
+    let entries = params::load(params::PARAMS_PATH)?;
+    let solution = solution.configured(&params::lookup(
+        &entries,
+        solution.day(),
+        &path,
+        &Params::new(),
+    ))?;
+    let input = input::read_file(&path)?;
+    let parsed = solution.parse(&input).map_err(|e| e.in_file(&path))?;
+    let (part1, part2) = (solution.part1(&*parsed)?, solution.part2(&*parsed)?);
+    println!("{}", part1);
+    println!("{}", part2);
+    Ok(())
+}
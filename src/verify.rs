@@ -0,0 +1,291 @@
+use std::{any::Any, panic};
+
+use serde::Serialize;
+
+use crate::{
+    answers::Expected,
+    days, input,
+    params::{self, Params, ParamsEntry},
+    verbose,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "result", content = "details", rename_all = "lowercase")]
+pub enum Outcome {
+    Pass,
+    Mismatch { expected: String, actual: String },
+    Error(String),
+    Panic(String),
+}
+
+impl Outcome {
+    pub fn is_pass(&self) -> bool {
+        *self == Outcome::Pass
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Outcome::Pass => "ok",
+            Outcome::Mismatch { .. } => "FAIL",
+            Outcome::Error(_) => "ERROR",
+            Outcome::Panic(_) => "PANIC",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub day: usize,
+    pub part: usize,
+    pub input: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+/// The whole of a `--verify` run, in the shape printed by `--format json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReport<'a> {
+    pub passed: usize,
+    pub failed: usize,
+    pub checks: &'a [Check],
+}
+
+impl<'a> VerifyReport<'a> {
+    pub fn new(checks: &'a [Check]) -> VerifyReport<'a> {
+        let failed = checks.iter().filter(|c| !c.outcome.is_pass()).count();
+        VerifyReport {
+            passed: checks.len() - failed,
+            failed,
+            checks,
+        }
+    }
+}
+
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs `f`, turning both errors and panics into a failed `Outcome`.
+fn guarded<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, Outcome> {
+    match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(Outcome::Error(e)),
+        Err(payload) => Err(Outcome::Panic(panic_message(payload))),
+    }
+}
+
+/// Solves one expected entry with the solver's parameters set to `params`,
+/// and compares every part it lists.
+pub fn check(expected: &Expected, params: &Params) -> Vec<Check> {
+    verbose!("Checking day {} against {}", expected.day, expected.input);
+    let parts: Vec<(usize, &str)> = (1..=2)
+        .filter_map(|part| expected.part(part).map(|answer| (part, answer)))
+        .collect();
+    let checks = |outcome: &dyn Fn(usize, &str) -> Outcome| {
+        parts
+            .iter()
+            .map(|&(part, answer)| Check {
+                day: expected.day,
+                part,
+                input: expected.input.clone(),
+                outcome: outcome(part, answer),
+            })
+            .collect()
+    };
+
+    let solution = match days::configured(expected.day, params) {
+        Ok(solution) => solution,
+        Err(e) => {
+            let msg = e.to_string();
+            return checks(&|_, _| Outcome::Error(msg.clone()));
+        }
+    };
+
+    let parsed = guarded(|| {
+        let input = input::read_file(expected.input_path()).map_err(|e| e.to_string())?;
+        solution.parse(&input).map_err(|e| {
+            e.in_file(expected.input_path().display().to_string())
+                .to_string()
+        })
+    });
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(outcome) => return checks(&|_, _| outcome.clone()),
+    };
+
+    checks(&|part, answer| match guarded(|| {
+        solution.part(part, &*parsed).map_err(|e| e.to_string())
+    }) {
+        Ok(actual) if actual.trim_end() == answer.trim_end() => Outcome::Pass,
+        Ok(actual) => Outcome::Mismatch {
+            expected: answer.to_string(),
+            actual,
+        },
+        Err(outcome) => outcome,
+    })
+}
+
+/// Checks every entry with the parameters the params file gives for its input,
+/// plus `overrides`. The panic hook is kept quiet so that a panicking day shows
+/// up in the table rather than splattered over stderr.
+pub fn check_all(expected: &[Expected], params: &[ParamsEntry], overrides: &Params) -> Vec<Check> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let checks = expected
+        .iter()
+        .flat_map(|e| check(e, &params::lookup(params, e.day, &e.input, overrides)))
+        .collect();
+    panic::set_hook(hook);
+    checks
+}
+
+/// A line-by-line diff of two answers, good enough for the short (or CRT
+/// screen shaped) strings the puzzles produce.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut out = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => out.push_str(&format!("    {}\n", e)),
+            (e, a) => {
+                if let Some(e) = e {
+                    out.push_str(&format!("  - {}\n", e));
+                }
+                if let Some(a) = a {
+                    out.push_str(&format!("  + {}\n", a));
+                }
+            }
+        }
+    }
+    out
+}
+
+pub fn print_table(checks: &[Check]) {
+    let input_width = checks
+        .iter()
+        .map(|c| c.input.len())
+        .chain(std::iter::once("input".len()))
+        .max()
+        .unwrap();
+
+    println!(
+        "{:>3}  {:>4}  {:<w$}  result",
+        "day",
+        "part",
+        "input",
+        w = input_width
+    );
+    for c in checks {
+        println!(
+            "{:>3}  {:>4}  {:<w$}  {}",
+            c.day,
+            c.part,
+            c.input,
+            c.outcome.label(),
+            w = input_width
+        );
+        match &c.outcome {
+            Outcome::Pass => (),
+            Outcome::Mismatch { expected, actual } => print!("{}", diff(expected, actual)),
+            Outcome::Error(msg) | Outcome::Panic(msg) => println!("    {}", msg),
+        }
+    }
+
+    let report = VerifyReport::new(checks);
+    println!();
+    println!("{} passed, {} failed", report.passed, report.failed);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn sample(day: usize, part1: &str) -> Expected {
+        serde_json::from_value(json!({
+            "day": day,
+            "input": format!("day{}-sample.txt", day),
+            "part1": part1,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn diffs_show_each_changed_line() {
+        assert_eq!(diff("42", "42"), "    42\n");
+        assert_eq!(diff("42", "43"), "  - 42\n  + 43\n");
+        assert_eq!(
+            diff("##..\n..##\n####", "##..\n.###"),
+            "    ##..\n  - ..##\n  + .###\n  - ####\n"
+        );
+        assert_eq!(diff("", "1"), "  + 1\n");
+    }
+
+    #[test]
+    fn outcomes_serialize_with_their_details() {
+        let check = |outcome| Check {
+            day: 3,
+            part: 2,
+            input: "day3.txt".to_string(),
+            outcome,
+        };
+        assert_eq!(
+            serde_json::to_value(check(Outcome::Pass)).unwrap(),
+            json!({ "day": 3, "part": 2, "input": "day3.txt", "result": "pass" })
+        );
+        assert_eq!(
+            serde_json::to_value(check(Outcome::Mismatch {
+                expected: "1".to_string(),
+                actual: "2".to_string(),
+            }))
+            .unwrap(),
+            json!({
+                "day": 3,
+                "part": 2,
+                "input": "day3.txt",
+                "result": "mismatch",
+                "details": { "expected": "1", "actual": "2" },
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(Outcome::Panic("oops".to_string())).unwrap(),
+            json!({ "result": "panic", "details": "oops" })
+        );
+    }
+
+    #[test]
+    fn checking_answers() {
+        let checks = check(&sample(1, "24000"), &Params::new());
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].outcome.is_pass());
+
+        let checks = check(&sample(1, "24001"), &Params::new());
+        assert_eq!(
+            checks[0].outcome,
+            Outcome::Mismatch {
+                expected: "24001".to_string(),
+                actual: "24000".to_string(),
+            }
+        );
+
+        let mut missing = sample(1, "1");
+        missing.input = "day1-missing.txt".to_string();
+        missing.part2 = Some("2".to_string());
+        let checks = check(&missing, &Params::new());
+        assert_eq!(checks.len(), 2);
+        assert!(checks
+            .iter()
+            .all(|c| matches!(&c.outcome, Outcome::Error(e) if e.contains("day1-missing.txt"))));
+
+        let report = VerifyReport::new(&checks);
+        assert_eq!((report.passed, report.failed), (0, 2));
+    }
+}
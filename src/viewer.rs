@@ -0,0 +1,383 @@
+use std::io::{self, Write};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+
+/// A day's puzzle run one step at a time, for watching in the viewer. Each
+/// step is whatever the day naturally counts in: a unit of sand, a rock, an
+/// instruction or a round.
+pub trait Simulation: SimulationClone {
+    /// Moves on to the next frame. Returns false, leaving everything as it
+    /// was, once there's nothing left to do.
+    fn step(&mut self) -> bool;
+
+    /// A line about where the simulation has got to, which breakpoints are
+    /// matched against.
+    fn status(&self) -> String;
+
+    /// The map as it stands, one string per row.
+    fn rows(&self) -> Vec<String>;
+
+    /// Where the first character of `rows` is on the map, for lining up
+    /// frames whose maps have grown in different directions.
+    fn origin(&self) -> (isize, isize) {
+        (0, 0)
+    }
+}
+
+/// Lets the viewer keep copies of a boxed `Simulation` to step back to.
+pub trait SimulationClone {
+    fn clone_box(&self) -> Box<dyn Simulation>;
+}
+
+impl<T: Simulation + Clone + 'static> SimulationClone for T {
+    fn clone_box(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
+
+/// How many steps apart the copies kept for stepping backwards are.
+const CHECKPOINT_INTERVAL: usize = 100;
+
+/// The most steps to take looking for a breakpoint, or towards a step to go
+/// to, before giving up.
+pub const RUN_LIMIT: usize = 100_000;
+
+/// The most cells a character on screen can stand for across, which is
+/// plenty for the largest maps.
+pub const MAX_ZOOM: usize = 1024;
+
+/// Cells that count as empty space when zoomed out, so that anything else in
+/// the same block shows up instead.
+const BACKGROUND: [char; 2] = ['.', ' '];
+
+/// Where a simulation is up to, and which part of its map is on screen.
+/// Stepping backwards replays from the nearest checkpoint, as none of the
+/// simulations can be run in reverse.
+pub struct Viewer {
+    /// The simulation at steps 0, `CHECKPOINT_INTERVAL`, `2 *
+    /// CHECKPOINT_INTERVAL` and so on, as far as it's been run.
+    checkpoints: Vec<Box<dyn Simulation>>,
+    current: Box<dyn Simulation>,
+    step: usize,
+    last: Option<usize>,
+    /// Each character on screen stands for a `zoom` by `zoom` block of cells.
+    zoom: usize,
+    /// The cell in the top left corner of the screen, as (column, row).
+    pan: (usize, usize),
+}
+
+impl Viewer {
+    pub fn new(simulation: Box<dyn Simulation>) -> Viewer {
+        Viewer {
+            checkpoints: vec![simulation.clone_box()],
+            current: simulation,
+            step: 0,
+            last: None,
+            zoom: 1,
+            pan: (0, 0),
+        }
+    }
+
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// The final step, if the simulation has been run that far.
+    pub fn last(&self) -> Option<usize> {
+        self.last
+    }
+
+    pub fn status(&self) -> String {
+        self.current.status()
+    }
+
+    pub fn rows(&self) -> Vec<String> {
+        self.current.rows()
+    }
+
+    /// Takes one step. Returns false if the simulation had already finished.
+    pub fn forward(&mut self) -> bool {
+        if self.last == Some(self.step) {
+            return false;
+        }
+        if !self.current.step() {
+            self.last = Some(self.step);
+            return false;
+        }
+
+        self.step += 1;
+        if self.step == self.checkpoints.len() * CHECKPOINT_INTERVAL {
+            self.checkpoints.push(self.current.clone_box());
+        }
+        true
+    }
+
+    /// Goes back one step. Returns false if already at the start.
+    pub fn back(&mut self) -> bool {
+        if self.step == 0 {
+            return false;
+        }
+        self.goto(self.step - 1);
+        true
+    }
+
+    /// Moves to step `target`, or to the last step if the simulation finishes
+    /// before it. Going forwards stops after `RUN_LIMIT` steps. Returns the
+    /// step reached.
+    pub fn goto(&mut self, target: usize) -> usize {
+        let target = target.min(self.step.saturating_add(RUN_LIMIT));
+        if target < self.step {
+            let idx = (target / CHECKPOINT_INTERVAL).min(self.checkpoints.len() - 1);
+            self.current = self.checkpoints[idx].clone_box();
+            self.step = idx * CHECKPOINT_INTERVAL;
+        }
+        while self.step < target && self.forward() {}
+        self.step
+    }
+
+    /// Steps forward until the status contains `pattern`, taking at least one
+    /// step. Returns false if the simulation finished, or `RUN_LIMIT` steps
+    /// went by, without finding it.
+    pub fn run_until(&mut self, pattern: &str) -> bool {
+        for _ in 0..RUN_LIMIT {
+            if !self.forward() {
+                return false;
+            }
+            if self.current.status().contains(pattern) {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn zoom(&self) -> usize {
+        self.zoom
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = (self.zoom / 2).max(1);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom * 2).min(MAX_ZOOM);
+    }
+
+    pub fn pan(&self) -> (usize, usize) {
+        self.pan
+    }
+
+    /// Moves the view by `dx` columns and `dy` rows of screen characters,
+    /// stopping at the top and left edges of the map.
+    pub fn pan_by(&mut self, dx: isize, dy: isize) {
+        let shift = |at: usize, by: isize| {
+            let cells = by * self.zoom as isize;
+            (at as isize + cells).max(0) as usize
+        };
+        self.pan = (shift(self.pan.0, dx), shift(self.pan.1, dy));
+    }
+
+    pub fn reset_pan(&mut self) {
+        self.pan = (0, 0);
+    }
+
+    /// The part of the map that fits in `width` by `height` characters at the
+    /// current zoom and pan. When zoomed out, a block shows the first thing in
+    /// it that isn't empty space.
+    pub fn view(&self, width: usize, height: usize) -> Vec<String> {
+        let cells: Vec<Vec<char>> = self
+            .current
+            .rows()
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        let zoom = self.zoom;
+        let block = |x0: usize, y0: usize| -> char {
+            let mut chars = cells[y0..(y0 + zoom).min(cells.len())]
+                .iter()
+                .flat_map(|row| row.iter().skip(x0).take(zoom));
+            let first = chars.next().copied();
+            first
+                .filter(|c| !BACKGROUND.contains(c))
+                .or_else(|| chars.copied().find(|c| !BACKGROUND.contains(c)))
+                .or(first)
+                .unwrap_or(' ')
+        };
+
+        (0..height)
+            .map(|sy| self.pan.1 + sy * zoom)
+            .take_while(|&y0| y0 < cells.len())
+            .map(|y0| {
+                let line: String = (0..width)
+                    .map(|sx| block(self.pan.0 + sx * zoom, y0))
+                    .collect();
+                line.trim_end().to_string()
+            })
+            .collect()
+    }
+}
+
+const HELP: &str =
+    "n/space next  p/backspace back  g go to step  b breakpoint  c continue  +/- zoom  \
+     arrows pan  home reset  q quit";
+
+/// A line being typed at the bottom of the screen.
+enum Prompt {
+    Goto(String),
+    Breakpoint(String),
+}
+
+/// Puts the terminal back the way it was, even after a panic.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<RawTerminal> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn fit(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+fn draw(viewer: &Viewer, title: &str, breakpoint: &Option<String>, bottom: &str) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let (width, height) = (width as usize, height as usize);
+    let last = match viewer.last() {
+        Some(last) => format!(" of {}", last),
+        None => String::new(),
+    };
+    let header = format!(
+        "{} | step {}{} | zoom 1:{} | at {},{}{}",
+        title,
+        viewer.step(),
+        last,
+        viewer.zoom(),
+        viewer.pan().0,
+        viewer.pan().1,
+        breakpoint
+            .as_ref()
+            .map_or(String::new(), |b| format!(" | break on {:?}", b)),
+    );
+
+    let mut out = io::stdout();
+    queue!(out, terminal::Clear(ClearType::All))?;
+    let map = viewer.view(width, height.saturating_sub(3));
+    let lines = vec![header, viewer.status()]
+        .into_iter()
+        .chain(map)
+        .chain(std::iter::repeat(String::new()))
+        .take(height.saturating_sub(1))
+        .chain(std::iter::once(bottom.to_string()));
+    for (y, line) in lines.enumerate() {
+        queue!(out, cursor::MoveTo(0, y as u16), Print(fit(&line, width)))?;
+    }
+    out.flush()
+}
+
+/// Shows `viewer` full screen until the user quits, stepping through it with
+/// the keys listed at the bottom of the screen.
+pub fn interact(viewer: &mut Viewer, title: &str) -> io::Result<()> {
+    let _terminal = RawTerminal::enter()?;
+    let mut prompt: Option<Prompt> = None;
+    let mut breakpoint: Option<String> = None;
+    let mut message = String::new();
+
+    loop {
+        let bottom = match &prompt {
+            Some(Prompt::Goto(typed)) => format!("Go to step: {}", typed),
+            Some(Prompt::Breakpoint(typed)) => format!("Break when the status contains: {}", typed),
+            None if !message.is_empty() => message.clone(),
+            None => HELP.to_string(),
+        };
+        draw(viewer, title, &breakpoint, &bottom)?;
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => key,
+            _ => continue,
+        };
+        message.clear();
+
+        if let Some(typing) = &mut prompt {
+            let typed = match typing {
+                Prompt::Goto(typed) | Prompt::Breakpoint(typed) => typed,
+            };
+            match key.code {
+                KeyCode::Char(c) => typed.push(c),
+                KeyCode::Backspace => {
+                    typed.pop();
+                }
+                KeyCode::Esc => prompt = None,
+                KeyCode::Enter => {
+                    match prompt.take().unwrap() {
+                        Prompt::Goto(typed) => match typed.trim().parse() {
+                            Ok(target) => {
+                                let reached = viewer.goto(target);
+                                if viewer.last() == Some(reached) && reached < target {
+                                    message = format!("Finished at step {}", reached);
+                                } else if reached < target {
+                                    message = format!(
+                                        "Stopped at step {} after {} steps",
+                                        reached, RUN_LIMIT
+                                    );
+                                }
+                            }
+                            Err(_) => message = format!("Not a step number: {}", typed),
+                        },
+                        Prompt::Breakpoint(typed) if typed.is_empty() => breakpoint = None,
+                        Prompt::Breakpoint(typed) => breakpoint = Some(typed),
+                    };
+                }
+                _ => (),
+            }
+            continue;
+        }
+
+        let (width, height) = terminal::size()?;
+        let (across, down) = ((width / 4).max(1) as isize, (height / 4).max(1) as isize);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('n') | KeyCode::Char(' ') => {
+                let moved = viewer.forward();
+                if !moved {
+                    message = "That was the last step".to_string();
+                }
+            }
+            KeyCode::Char('p') | KeyCode::Backspace => {
+                viewer.back();
+            }
+            KeyCode::Char('g') => prompt = Some(Prompt::Goto(String::new())),
+            KeyCode::Char('b') => prompt = Some(Prompt::Breakpoint(String::new())),
+            KeyCode::Char('c') => match &breakpoint {
+                Some(pattern) => {
+                    if !viewer.run_until(pattern) {
+                        message = format!("Stopped at step {} without a match", viewer.step());
+                    }
+                }
+                None => message = "No breakpoint set; press b to set one".to_string(),
+            },
+            KeyCode::Char('+') | KeyCode::Char('=') => viewer.zoom_in(),
+            KeyCode::Char('-') => viewer.zoom_out(),
+            KeyCode::Left => viewer.pan_by(-across, 0),
+            KeyCode::Right => viewer.pan_by(across, 0),
+            KeyCode::Up => viewer.pan_by(0, -down),
+            KeyCode::Down => viewer.pan_by(0, down),
+            KeyCode::Home => viewer.reset_pan(),
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use inotify::{Inotify, WatchDescriptor, WatchMask};
+
+use crate::report::SolveReport;
+
+/// How often to check for changes while waiting.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long things have to stay quiet after a change before it counts, so
+/// that an editor saving several files, or a file in several writes, only
+/// causes one re-run.
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// What a watched directory is being watched for.
+enum Interest {
+    /// Only the files with these names.
+    Files(Vec<PathBuf>),
+    /// Any Rust source file.
+    Sources,
+}
+
+/// Waits for files to change, using inotify. Directories are watched rather
+/// than the files in them, as many editors save by writing a new file and
+/// renaming it over the old one, which a watch on the old file wouldn't see.
+pub struct Watcher {
+    inotify: Inotify,
+    dirs: Vec<(WatchDescriptor, PathBuf, Interest)>,
+    buffer: Vec<u8>,
+}
+
+impl Watcher {
+    /// Watches each of `files`, and every `.rs` file directly inside each of
+    /// `source_dirs`.
+    pub fn new(files: &[PathBuf], source_dirs: &[PathBuf]) -> io::Result<Watcher> {
+        let mut watcher = Watcher {
+            inotify: Inotify::init()?,
+            dirs: Vec::new(),
+            buffer: vec![0; 4096],
+        };
+
+        let mut by_dir: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        for file in files {
+            let dir = match file.parent() {
+                Some(dir) if dir != Path::new("") => dir.to_path_buf(),
+                _ => PathBuf::from("."),
+            };
+            by_dir.entry(dir).or_default().push(file.clone());
+        }
+        for (dir, files) in by_dir {
+            watcher.add(dir, Interest::Files(files))?;
+        }
+        for dir in source_dirs {
+            watcher.add(dir.clone(), Interest::Sources)?;
+        }
+        Ok(watcher)
+    }
+
+    fn add(&mut self, dir: PathBuf, interest: Interest) -> io::Result<()> {
+        let mask = WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::DELETE;
+        let wd = self
+            .inotify
+            .watches()
+            .add(&dir, mask)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", dir.display(), e)))?;
+        self.dirs.push((wd, dir, interest));
+        Ok(())
+    }
+
+    /// Any watched files that have changed since the last call, without
+    /// waiting.
+    fn changed(&mut self) -> io::Result<Vec<PathBuf>> {
+        let events = match self.inotify.read_events(&mut self.buffer) {
+            Ok(events) => events,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut changed = Vec::new();
+        for event in events {
+            let name = match event.name {
+                Some(name) => name,
+                None => continue,
+            };
+            let (_, dir, interest) = match self.dirs.iter().find(|(wd, _, _)| *wd == event.wd) {
+                Some(dir) => dir,
+                None => continue,
+            };
+            let path = dir.join(name);
+            let wanted = match interest {
+                Interest::Files(files) => files.iter().any(|f| f.file_name() == Some(name)),
+                Interest::Sources => path.extension() == Some(OsStr::new("rs")),
+            };
+            if wanted && !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Waits for watched files to change, returning the ones that did once
+    /// they've settled down. Gives up with nothing after `timeout`, if there
+    /// is one.
+    pub fn wait(&mut self, timeout: Option<Duration>) -> io::Result<Vec<PathBuf>> {
+        let start = Instant::now();
+        let mut changed: Vec<PathBuf> = Vec::new();
+        let mut last_change = start;
+        loop {
+            let now = Instant::now();
+            if !changed.is_empty() && now - last_change >= SETTLE_TIME {
+                return Ok(changed);
+            }
+            if changed.is_empty() && timeout.is_some_and(|t| now - start >= t) {
+                return Ok(changed);
+            }
+
+            let new = self.changed()?;
+            if new.is_empty() {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            last_change = Instant::now();
+            for path in new {
+                if !changed.contains(&path) {
+                    changed.push(path);
+                }
+            }
+        }
+    }
+}
+
+/// Builds and runs the solver for `day` on `input` with cargo, so that
+/// changes to the code are picked up. `extra_args` are passed on to it. The
+/// build's own output goes straight to stderr.
+pub fn solve_with_cargo(
+    day: usize,
+    input: &Path,
+    extra_args: &[String],
+) -> Result<SolveReport, String> {
+    let output = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["run", "--release", "--quiet", "--bin", "aoc", "--"])
+        .args([
+            "--day",
+            &day.to_string(),
+            "--format",
+            "json",
+            "-q",
+            "--input",
+        ])
+        .arg(input)
+        .args(extra_args)
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("Couldn't run cargo: {}", e))?;
+
+    // A failed part still prints a report, but a failed build doesn't
+    serde_json::from_slice(&output.stdout).map_err(|_| match output.status.code() {
+        Some(code) => format!("The build or run failed (exit status {})", code),
+        None => "The build or run was killed".to_string(),
+    })
+}
+
+/// The answers to one run of a day, by input name and part, with an error in
+/// place of any answer that couldn't be found.
+pub type Answers = HashMap<(String, usize), Result<String, String>>;
+
+/// The answers in `report`, which was asked for `parts`. A parse error counts
+/// against each of them.
+pub fn answers(report: &SolveReport, parts: &[usize]) -> Answers {
+    let mut answers = Answers::new();
+    for (idx, phase) in report.parts.iter().enumerate() {
+        let part = phase.part.unwrap_or(idx + 1);
+        let answer = match (&phase.answer, &phase.error) {
+            (Some(answer), _) => Ok(answer.clone()),
+            (None, Some(error)) => Err(error.clone()),
+            (None, None) => Err("no answer".to_string()),
+        };
+        answers.insert((report.input.clone(), part), answer);
+    }
+    if let Some(error) = &report.parse.error {
+        for &part in parts {
+            answers.insert((report.input.clone(), part), Err(error.clone()));
+        }
+    }
+    answers
+}
+
+/// A table of `current`'s answers, in the order of `inputs`, each with how it
+/// compares to the one in `previous`. Answers over several lines, like day
+/// 10's, go underneath their row.
+pub fn compare(inputs: &[String], current: &Answers, previous: &Answers) -> String {
+    let width = inputs.iter().map(|i| i.len()).max().unwrap_or(0).max(5);
+    let mut table = format!("{:width$}  part  answer\n", "input", width = width);
+    for input in inputs {
+        for part in 1..=2 {
+            let key = (input.clone(), part);
+            let answer = match current.get(&key) {
+                Some(answer) => answer,
+                None => continue,
+            };
+            let change = match previous.get(&key) {
+                None => String::new(),
+                Some(before) if before == answer => " (unchanged)".to_string(),
+                Some(Ok(before)) if !before.contains('\n') => format!(" (was {})", before),
+                Some(_) => " (changed)".to_string(),
+            };
+            let row = match answer {
+                Ok(answer) if answer.contains('\n') => format!("{}\n{}", change.trim(), answer),
+                Ok(answer) => format!("{}{}", answer, change),
+                Err(error) => format!("error: {}{}", error, change),
+            };
+            table.push_str(&format!(
+                "{:width$}  {:>4}  {}\n",
+                input,
+                part,
+                row,
+                width = width
+            ));
+        }
+    }
+    table
+}
@@ -0,0 +1,135 @@
+//! With checked arithmetic turned on, numbers that grow too big for their type
+//! come back as errors naming the day and the calculation, while the samples
+//! still solve as usual.
+
+use aoc2022::{
+    checked::{self, Overflow},
+    days,
+    params::Params,
+};
+use serde_json::json;
+
+/// Solves `part` of `day`, turning checking on first. Every test in this file
+/// wants it on, so it doesn't matter that they share the switch.
+fn solve(day: usize, params: &Params, input: &str, part: usize) -> Result<String, String> {
+    checked::set_enabled(true);
+    let solution = days::configured(day, params).unwrap();
+    let parsed = solution.parse(input).map_err(|e| e.to_string())?;
+    solution.part(part, &*parsed).map_err(|e| e.to_string())
+}
+
+#[test]
+fn helpers_describe_the_operation() {
+    checked::set_enabled(true);
+    assert_eq!(checked::mul(11, "worry level", 3usize, 4), Ok(12));
+    let overflow = checked::mul(11, "worry level", usize::MAX, 2).unwrap_err();
+    assert_eq!(
+        overflow.to_string(),
+        format!("Day 11: overflow in worry level ({} * 2)", usize::MAX)
+    );
+
+    assert_eq!(
+        checked::div(21, "monkey abcd's number", 7isize, 0),
+        Err(Overflow {
+            day: 21,
+            operation: "monkey abcd's number (7 / 0)".to_string(),
+            division_by_zero: true,
+        })
+    );
+    assert!(checked::pow(25, "place value", 5i64, 28).is_err());
+    assert!(checked::sum(25, "total", vec![i64::MAX, 1]).is_err());
+}
+
+#[test]
+fn samples_still_solve() {
+    let params = Params::new();
+    for &(day, input, part1) in &[
+        (11, include_str!("../data/day11-sample.txt"), "10605"),
+        (20, include_str!("../data/day20-sample.txt"), "3"),
+        (21, include_str!("../data/day21-sample.txt"), "152"),
+        (25, include_str!("../data/day25-sample.txt"), "2=-1=0"),
+    ] {
+        assert_eq!(solve(day, &params, input, 1).unwrap(), part1);
+    }
+}
+
+#[test]
+fn day11_worry_levels() {
+    let input = "Monkey 0:
+  Starting items: 5000000000
+  Operation: new = old * old
+  Test: divisible by 2
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 1
+  Operation: new = old + 1
+  Test: divisible by 2
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+    let error = solve(11, &Params::new(), input, 1).unwrap_err();
+    assert!(
+        error.starts_with("Day 11: overflow in worry level"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn day20_decryption_key() {
+    let params = json!({ "decryption_key": isize::MAX / 2 });
+    let error = solve(
+        20,
+        params.as_object().unwrap(),
+        include_str!("../data/day20-sample.txt"),
+        2,
+    )
+    .unwrap_err();
+    assert!(
+        error.starts_with("Day 20: overflow in decryption key"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn day21_monkey_numbers() {
+    let input = format!(
+        "root: aaaa * bbbb\naaaa: {}\nbbbb: 2\nhumn: 1\n",
+        isize::MAX
+    );
+    let error = solve(21, &Params::new(), &input, 1).unwrap_err();
+    assert!(
+        error.starts_with("Day 21: overflow in monkey root's number"),
+        "{}",
+        error
+    );
+
+    let input = "root: aaaa / bbbb\naaaa: 5\nbbbb: 0\nhumn: 1\n";
+    let error = solve(21, &Params::new(), input, 1).unwrap_err();
+    assert_eq!(
+        error,
+        "Day 21: division by zero in monkey root's number (5 / 0)"
+    );
+}
+
+#[test]
+fn day25_long_numbers() {
+    // 28 SNAFU digits is more than an i64 can hold, which is always a parse
+    // error
+    let error = solve(25, &Params::new(), &"2".repeat(28), 1).unwrap_err();
+    assert_eq!(
+        error,
+        format!("line 1: number too long in {:?}", "2".repeat(28))
+    );
+
+    let input = format!("{}\n", "2".repeat(27)).repeat(3);
+    let error = solve(25, &Params::new(), &input, 1).unwrap_err();
+    assert!(
+        error.starts_with("Day 25: overflow in sum of fuel requirements"),
+        "{}",
+        error
+    );
+}
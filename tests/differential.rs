@@ -0,0 +1,43 @@
+//! Runs every optimized solver that has a brute-force reference against it on
+//! random inputs.
+
+use aoc2022::{days, differential};
+
+/// Enough to catch the shortcuts going wrong without making the test suite
+/// slow; `aoc --differential` runs more.
+const CASES: usize = 200;
+const MAX_SIZE: usize = 10;
+
+fn check_day(day: usize) {
+    let reference = days::DIFFERENTIAL
+        .iter()
+        .find(|d| d.day() == day)
+        .unwrap_or_else(|| panic!("no reference solver for day {}", day));
+    let report = differential::run(*reference, 0, CASES, MAX_SIZE);
+    if let Some(m) = report.mismatch {
+        panic!(
+            "day {} {} disagrees on case seed {}:\n{}\noptimized: {}\nreference: {}",
+            day, report.name, m.seed, m.case, m.optimized, m.reference
+        );
+    }
+}
+
+#[test]
+fn day15() {
+    check_day(15);
+}
+
+#[test]
+fn day17() {
+    check_day(17);
+}
+
+#[test]
+fn day19() {
+    check_day(19);
+}
+
+#[test]
+fn day20() {
+    check_day(20);
+}
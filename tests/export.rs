@@ -0,0 +1,161 @@
+//! Checks the image encoders byte by byte on tiny pictures, and that the days
+//! draw pictures of the right shape from their samples.
+
+use std::{env, fs, process};
+
+use aoc2022::{
+    days,
+    export::{self, ExportOptions, Format, Image, Rgb},
+    viewer::Simulation,
+};
+
+/// A red, a white and a black pixel side by side.
+fn strip() -> Image {
+    let mut image = Image::new(3, 1, Rgb::WHITE);
+    image.set(0, 0, Rgb(255, 0, 0));
+    image.set(2, 0, Rgb::BLACK);
+    image
+}
+
+#[test]
+fn ppm() {
+    let mut expected = b"P6\n3 1\n255\n".to_vec();
+    expected.extend_from_slice(&[255, 0, 0, 255, 255, 255, 0, 0, 0]);
+    assert_eq!(export::encode(&strip(), Format::Ppm, 1), expected);
+
+    let scaled = export::encode(&strip(), Format::Ppm, 2);
+    assert!(scaled.starts_with(b"P6\n6 2\n255\n"));
+    assert_eq!(scaled.len(), b"P6\n6 2\n255\n".len() + 6 * 2 * 3);
+}
+
+#[test]
+fn pbm() {
+    // Red is dark enough to come out black
+    assert_eq!(
+        export::encode(&strip(), Format::Pbm, 1),
+        b"P4\n3 1\n\xa0".to_vec()
+    );
+    // Each row starts on a new byte
+    assert_eq!(
+        export::encode(&strip(), Format::Pbm, 3),
+        b"P4\n9 3\n\xe3\x80\xe3\x80\xe3\x80".to_vec()
+    );
+}
+
+#[test]
+fn svg() {
+    let mut image = Image::new(3, 2, Rgb::WHITE);
+    image.set(2, 1, Rgb(0, 128, 255));
+    let svg = String::from_utf8(export::encode(&image, Format::Svg, 10)).unwrap();
+    assert!(svg.contains("width=\"30\" height=\"20\" viewBox=\"0 0 3 2\""));
+    let rects: Vec<&str> = svg.lines().filter(|l| l.starts_with("<rect")).collect();
+    assert_eq!(
+        rects,
+        vec![
+            "<rect x=\"0\" y=\"0\" width=\"3\" height=\"1\" fill=\"#ffffff\"/>",
+            "<rect x=\"0\" y=\"1\" width=\"2\" height=\"1\" fill=\"#ffffff\"/>",
+            "<rect x=\"2\" y=\"1\" width=\"1\" height=\"1\" fill=\"#0080ff\"/>",
+        ]
+    );
+}
+
+/// A line of `#`s growing a cell to the left each step.
+#[derive(Clone)]
+struct Growing {
+    length: usize,
+}
+
+impl Simulation for Growing {
+    fn step(&mut self) -> bool {
+        self.length += 1;
+        self.length < 4
+    }
+
+    fn status(&self) -> String {
+        format!("length {}", self.length)
+    }
+
+    fn rows(&self) -> Vec<String> {
+        vec!["#".repeat(self.length)]
+    }
+
+    fn origin(&self) -> (isize, isize) {
+        (-(self.length as isize), 0)
+    }
+}
+
+#[test]
+fn animation_frames_line_up() {
+    let frames: Vec<Image> = export::animate(Box::new(Growing { length: 1 }), 10).collect();
+    assert_eq!(frames.len(), 3);
+    for (idx, frame) in frames.iter().enumerate() {
+        assert_eq!((frame.width(), frame.height()), (3, 1));
+        // The right hand end stays put
+        let filled: Vec<bool> = (0..3).map(|x| frame.get(x, 0) != Rgb::WHITE).collect();
+        let expected: Vec<bool> = (0..3).map(|x| x >= 2 - idx).collect();
+        assert_eq!(filled, expected);
+    }
+
+    assert_eq!(
+        export::animate(Box::new(Growing { length: 1 }), 1).count(),
+        2
+    );
+}
+
+#[test]
+fn writing_frames() {
+    let dir = env::temp_dir().join(format!("aoc-export-{}", process::id()));
+    let options = ExportOptions {
+        format: Format::Pbm,
+        scale: 1,
+        every: 2,
+    };
+
+    let one = export::write_frames(vec![strip()].into_iter(), &dir, "one", options).unwrap();
+    assert_eq!(one, vec![dir.join("one.pbm").display().to_string()]);
+
+    let frames = vec![strip(); 4].into_iter();
+    let many = export::write_frames(frames, &dir, "many", options).unwrap();
+    // Every other frame, and the last
+    assert_eq!(many.len(), 3);
+    assert!(many[2].ends_with("many-00002.pbm"));
+    assert_eq!(fs::read(&many[1]).unwrap(), b"P4\n3 1\n\xa0");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+fn day_frames(day: usize, input: &str, part: usize, steps: usize) -> Vec<Image> {
+    let solution = days::get(day).unwrap();
+    let parsed = solution.parse(input).unwrap();
+    solution.frames(&*parsed, part, steps).unwrap().collect()
+}
+
+#[test]
+fn day_pictures() {
+    let day8 = day_frames(8, include_str!("../data/day8-sample.txt"), 2, 0);
+    assert_eq!(day8.len(), 1);
+    assert_eq!((day8[0].width(), day8[0].height()), (5, 5));
+    // The most scenic tree is the one in the middle of the fourth row
+    assert_eq!(day8[0].get(2, 3), Rgb(208, 32, 32));
+
+    let day12 = day_frames(12, include_str!("../data/day12-sample.txt"), 1, 0);
+    assert_eq!((day12[0].width(), day12[0].height()), (8, 5));
+
+    // One slice for each layer, with room for the steam around the droplet
+    let day18 = day_frames(18, include_str!("../data/day18-sample.txt"), 2, 0);
+    assert_eq!(day18.len(), 6 + 2);
+    assert_eq!((day18[0].width(), day18[0].height()), (3 + 2, 3 + 2));
+}
+
+#[test]
+fn day_animations() {
+    let day14 = day_frames(14, include_str!("../data/day14-sample.txt"), 1, 1000);
+    // Before any sand, then each of the 24 units
+    assert_eq!(day14.len(), 25);
+    let day9 = day_frames(9, include_str!("../data/day9-sample.txt"), 1, 5);
+    assert_eq!(day9.len(), 6);
+
+    let solution = days::get(1).unwrap();
+    let parsed = solution.parse("1\n").unwrap();
+    assert!(solution.frames(&*parsed, 1, 10).is_err());
+}
@@ -0,0 +1,201 @@
+//! Feeds every day's parser mutated copies of its sample input. Malformed
+//! input has to come back as an error, never a panic.
+
+use aoc2022::{days, fuzz};
+
+/// The error from parsing `input` as day `day`'s, which has to fail.
+fn parse_error(day: usize, input: &str) -> String {
+    match days::get(day).unwrap().parse(input) {
+        Ok(_) => panic!("day {} parsed {:?}", day, input),
+        Err(e) => e.to_string(),
+    }
+}
+
+/// Enough to catch a parser that indexes or unwraps without checking, while
+/// keeping the test suite fast; `aoc --fuzz` runs more.
+const CASES: usize = 1000;
+
+#[test]
+fn no_parser_panics() {
+    let crashes: Vec<String> = days::ALL
+        .iter()
+        .filter_map(|&day| fuzz::run(day, 0, CASES).crash)
+        .map(|crash| {
+            format!(
+                "day {} panicked on case seed {}: {}\n{:?}",
+                crash.day, crash.seed, crash.message, crash.input
+            )
+        })
+        .collect();
+    assert!(crashes.is_empty(), "{}", crashes.join("\n"));
+}
+
+#[test]
+fn snafu_numbers_too_long_for_an_i64() {
+    // Longer than the mutator ever makes a line, and an error even when
+    // --checked is off
+    let line = "1".repeat(30);
+    assert_eq!(
+        parse_error(25, &format!("1=\n{}\n", line)),
+        format!("line 2: number too long in {:?}", line)
+    );
+}
+
+#[test]
+fn snafu_totals_of_zero_and_below() {
+    let day25 = days::get(25).unwrap();
+    for (input, total) in [
+        ("-\n", "-"),
+        ("1\n-\n", "0"),
+        ("=\n-\n", "-2"),
+        ("=\n", "="),
+    ] {
+        let parsed = day25.parse(input).unwrap();
+        assert_eq!(day25.part1(&*parsed).unwrap(), total, "{:?}", input);
+    }
+    let parsed = day25.parse(&format!("-{}\n", "0".repeat(26))).unwrap();
+    assert_eq!(
+        day25.part1(&*parsed).unwrap(),
+        format!("-{}", "0".repeat(26))
+    );
+}
+
+#[test]
+fn a_single_number_to_mix() {
+    assert_eq!(parse_error(20, "1\n"), "expected at least two numbers");
+    assert_eq!(parse_error(20, ""), "expected at least two numbers");
+}
+
+#[test]
+fn monkeys_dividing_by_zero() {
+    let input =
+        include_str!("../data/day11-sample.txt").replacen("divisible by 23", "divisible by 0", 1);
+    assert_eq!(
+        parse_error(11, &input),
+        "line 4: can't test for divisibility by 0 in \"Test: divisible by 0\""
+    );
+}
+
+#[test]
+fn maps_without_room_to_walk() {
+    let day22 = days::get(22).unwrap();
+    // Without portals, the cube has no edges to cross
+    let parsed = day22.parse("#.#\n\n10R5\n").unwrap();
+    assert_eq!(day22.part1(&*parsed).unwrap(), "5");
+    assert_eq!(
+        day22.part2(&*parsed).unwrap_err().to_string(),
+        "Fell off the edge of the map at 1,1"
+    );
+
+    assert_eq!(
+        parse_error(22, "  ##\n  #.\n\n10R5\n"),
+        "line 1: no open tile to start on in the top row of the map in \"  ##\""
+    );
+}
+
+#[test]
+fn totals_too_big_to_add_up() {
+    assert_eq!(
+        parse_error(1, "9999999999999999999\n9999999999999999999\n"),
+        "line 1: calorie total overflows in \"9999999999999999999\""
+    );
+    assert_eq!(
+        parse_error(
+            7,
+            "$ cd /\n$ ls\ndir a\n18446744073709551615 b.txt\n$ cd a\n$ ls\n1 c.txt\n"
+        ),
+        "size of directory / overflows"
+    );
+}
+
+#[test]
+fn valleys_too_wide_for_their_blizzards() {
+    let valley = |width: usize| {
+        format!(
+            "#.{0}\n#{1}#\n{0}.#\n",
+            "#".repeat(width),
+            ".".repeat(width)
+        )
+    };
+    assert_eq!(parse_error(24, &valley(256)), "valley is too wide");
+
+    let day24 = days::get(24).unwrap();
+    let parsed = day24.parse(&valley(255)).unwrap();
+    assert_eq!(day24.part1(&*parsed).unwrap(), "256");
+}
+
+#[test]
+fn caves_without_flowing_valves() {
+    let day16 = days::get(16).unwrap();
+    for input in &[
+        "Valve AA has flow rate=0; tunnel leads to valve AA\n",
+        "Valve AA has flow rate=0; tunnel leads to valve BB\n\
+         Valve BB has flow rate=0; tunnel leads to valve AA\n",
+    ] {
+        let parsed = day16.parse(input).unwrap();
+        assert_eq!(day16.part1(&*parsed).unwrap(), "0");
+        assert_eq!(day16.part2(&*parsed).unwrap(), "0");
+    }
+}
+
+#[test]
+fn monkeys_that_cant_be_worked_out() {
+    let day21 = days::get(21).unwrap();
+    let errors = |input: &str| {
+        let parsed = day21.parse(input).unwrap();
+        (
+            day21.part1(&*parsed).map_err(|e| e.to_string()),
+            day21.part2(&*parsed).map_err(|e| e.to_string()),
+        )
+    };
+
+    // Even with --checked off
+    assert_eq!(
+        errors("root: aaaa + bbbb\naaaa: humn / bbbb\nbbbb: 0\nhumn: 5\n").0,
+        Err("Day 21: division by zero in monkey aaaa's number (5 / 0)".to_string())
+    );
+    assert_eq!(
+        errors("root: aaaa + bbbb\naaaa: humn * bbbb\nbbbb: 0\nhumn: 5\n").1,
+        Err("Day 21: division by zero in working back to humn (0 / 0)".to_string())
+    );
+
+    assert_eq!(
+        errors("root: aaaa + humn\naaaa: humn * humn\nhumn: 5\n"),
+        (
+            Ok("30".to_string()),
+            Err("Neither side of root's equality is known".to_string())
+        )
+    );
+    assert_eq!(
+        errors("root: aaaa + bbbb\naaaa: humn * humn\nbbbb: 4\nhumn: 5\n").1,
+        Err("Neither humn nor humn can be worked out without humn".to_string())
+    );
+    assert_eq!(
+        errors("root: aaaa + bbbb\naaaa: 3\nbbbb: 4\nhumn: 5\n").1,
+        Err("Neither side of root's equality needs humn".to_string())
+    );
+    assert_eq!(
+        errors("root: 7\nhumn: 5\n"),
+        (
+            Ok("7".to_string()),
+            Err("root has no two monkeys to compare".to_string())
+        )
+    );
+    assert_eq!(
+        errors("root: humn + aaaa\naaaa: 3\nhumn: 5\n").1,
+        Ok("3".to_string())
+    );
+    assert_eq!(
+        errors("root: aaaa + bbbb\naaaa: root + humn\nbbbb: 4\nhumn: 5\n").1,
+        Err("Neither root nor humn can be worked out without humn".to_string())
+    );
+
+    assert_eq!(
+        parse_error(21, "root: aaaa + bbbb\naaaa: 1\nbbbb: 2\nhumn: 3\ncc: 4\n"),
+        "line 5: bad name \"cc\" (monkey names are four letters, not \"cc\") in \"cc: 4\""
+    );
+    assert_eq!(
+        parse_error(21, "root: aaaa + bbbb\naaaa: 1\nbbbb: 2\n"),
+        "no monkey named humn"
+    );
+}
@@ -0,0 +1,77 @@
+//! The HTML report: what it shows for the days with pictures, screens and
+//! cycles, and that it stands on its own.
+
+use aoc2022::{
+    days,
+    html::{self, DayReport, MAX_PICTURES},
+};
+
+fn sample_report(day: usize, steps: usize) -> DayReport {
+    let input = std::fs::read_to_string(format!("data/day{}-sample.txt", day)).unwrap();
+    html::day_report(days::get(day).unwrap(), "sample", &input, steps)
+}
+
+#[test]
+fn escaping() {
+    assert_eq!(
+        html::escape("<a href=\"x\">Tom & Jerry's</a>"),
+        "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+    );
+}
+
+#[test]
+fn screens_go_in_the_days_section() {
+    let day10 = sample_report(10, 10);
+    assert!(day10.pictures.is_empty());
+    let page = html::render("Samples", &[day10]);
+
+    let (summary, section) = page.split_once("<section id=\"day10\">").unwrap();
+    assert!(summary.contains("<td>13140</td><td><a href=\"#day10\">(see below)</a></td>"));
+    assert!(section.contains("<pre>##..##..##..##..##..##..##..##..##..##..\n"));
+}
+
+#[test]
+fn cycles_are_spelled_out() {
+    let page = html::render("Samples", &[sample_report(17, 10)]);
+    assert!(page.contains("cycle: delta 53, period 35, start 28"));
+}
+
+#[test]
+fn pictures_are_spread_over_the_steps() {
+    // A grain of day 14's sand comes to rest each step, 24 of them in all
+    let day14 = sample_report(14, 1000);
+    let frames: Vec<usize> = day14
+        .pictures
+        .iter()
+        .filter(|p| p.part == 1)
+        .map(|p| p.frame)
+        .collect();
+    assert!(frames.len() <= MAX_PICTURES + 1, "{:?}", frames);
+    assert_eq!(frames[0], 0);
+    assert_eq!(frames.last(), Some(&24));
+    assert!(frames.windows(2).all(|w| w[0] < w[1]));
+
+    // Days with a few pictures of their own show them all
+    let day12 = sample_report(12, 1000);
+    assert_eq!(day12.pictures.len(), 2);
+    assert!(day12.pictures[0].svg.starts_with("<svg"));
+}
+
+#[test]
+fn the_page_stands_alone() {
+    let page = html::render(
+        "Samples <all>",
+        &[sample_report(8, 10), sample_report(1, 10)],
+    );
+    assert!(page.starts_with("<!DOCTYPE html>"));
+    assert!(page.contains("<title>Samples &lt;all&gt;</title>"));
+    assert!(page.contains("<a href=\"#day8\">8</a></td><td>21</td><td>8</td>"));
+    assert!(page.find("id=\"day8\"") < page.find("id=\"day1\""));
+
+    // Nothing is fetched from anywhere else
+    for reference in ["<script", "<link", "<img", "src=", "url("] {
+        assert!(!page.contains(reference), "{}", reference);
+    }
+    let without_namespaces = page.replace("xmlns=\"http://www.w3.org/2000/svg\"", "");
+    assert!(!without_namespaces.contains("http"));
+}
@@ -0,0 +1,144 @@
+//! Reading lines, blocks, grids and sections from anything readable, and
+//! finding the right line to blame when they don't parse.
+
+use std::io;
+
+use aoc2022::{
+    error::{ErrorKind, InputError},
+    input::{self, Block},
+};
+
+const MONKEYS: &str = "Monkey 0:
+  Starting items: 79, 98
+
+Monkey 1:
+  Starting items: 54
+
+
+Monkey 2:
+  Starting items: nine
+";
+
+/// A reader that fails straight away.
+struct Broken;
+
+impl io::Read for Broken {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("disconnected"))
+    }
+}
+
+fn number(line: &str) -> Result<usize, InputError> {
+    Ok(line.parse()?)
+}
+
+fn items(block: &Block) -> Result<Vec<usize>, InputError> {
+    let line = block.lines[1].trim();
+    let items = line
+        .strip_prefix("Starting items: ")
+        .ok_or_else(|| InputError::malformed("expected the starting items"))?;
+    items
+        .split(", ")
+        .map(number)
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.at(2, line))
+}
+
+#[test]
+fn lines() {
+    let numbers: Vec<usize> = input::read_lines("1\n\n  2\n3\n".as_bytes(), number).unwrap();
+    assert_eq!(numbers, vec![1, 2, 3]);
+
+    let error = input::read_lines::<_, Vec<_>>("1\n2\nx\n".as_bytes(), number).unwrap_err();
+    assert_eq!(error.line, Some((3, "x".to_string())));
+
+    let error = input::read_lines::<_, Vec<_>>(Broken, number).unwrap_err();
+    assert!(matches!(error.kind, ErrorKind::Io(_)));
+}
+
+#[test]
+fn blocks() {
+    let blocks = input::blocks(MONKEYS);
+    assert_eq!(blocks.len(), 3);
+    assert_eq!(
+        blocks[1],
+        Block {
+            first_line: 4,
+            lines: vec!["Monkey 1:", "  Starting items: 54"],
+        }
+    );
+    assert_eq!(blocks[2].first_line, 8);
+    assert_eq!(blocks[2].text(), "Monkey 2:\n  Starting items: nine");
+
+    let parsed: Vec<Vec<usize>> =
+        input::parse_blocks(MONKEYS.split("Monkey 2").next().unwrap(), items).unwrap();
+    assert_eq!(parsed, vec![vec![79, 98], vec![54]]);
+
+    // Errors from inside a block are numbered from the top of the input
+    let error = input::read_blocks::<_, Vec<_>>(MONKEYS.as_bytes(), items).unwrap_err();
+    assert_eq!(error.line, Some((9, "Starting items: nine".to_string())));
+    // and ones that aren't about a particular line go on the block's first
+    let error = input::parse_blocks::<_, Vec<()>>(MONKEYS, |_| {
+        Err(InputError::malformed("no monkeys allowed"))
+    })
+    .unwrap_err();
+    assert_eq!(error.line, Some((1, "Monkey 0:".to_string())));
+}
+
+#[test]
+fn sections() {
+    let map_and_path = "  ..#\n  .#.\n\n10R5\n";
+    let (map, path) = input::read_sections(
+        map_and_path.as_bytes(),
+        |block| {
+            Ok(block
+                .lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>())
+        },
+        |block| Ok(block.text()),
+    )
+    .unwrap();
+    assert_eq!(map, vec!["  ..#", "  .#."]);
+    assert_eq!(path, "10R5");
+
+    assert!(input::sections("  ..#\n  .#.\n").is_err());
+    assert!(input::sections(MONKEYS).is_err());
+}
+
+#[test]
+fn grids() {
+    let digit = |c: char| c.to_digit(10).ok_or_else(|| InputError::unknown_token(c));
+    let grid = input::read_grid("123\n456\n".as_bytes(), digit).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+
+    let error = input::read_grid("123\n4x6\n".as_bytes(), digit).unwrap_err();
+    assert_eq!(error.line, Some((2, "4x6".to_string())));
+    assert_eq!(error.column, Some(2));
+}
+
+#[test]
+fn columns() {
+    let word = |line: &str| match line.split_once(' ') {
+        Some(("add", n)) => number(n),
+        _ => Err(InputError::unknown_token(line.split(' ').next().unwrap())),
+    };
+
+    // Counted from the start of the line as written, indentation and all
+    let error = input::parse_lines::<_, Vec<_>>("add 1\n   sub 2\n", word).unwrap_err();
+    assert_eq!(error.column, Some(4));
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 4: unknown token \"sub\" in \"sub 2\""
+    );
+    assert_eq!(
+        error.in_file("day1.txt").to_string(),
+        "day1.txt:2:4: unknown token \"sub\" in \"sub 2\""
+    );
+
+    // Other errors aren't pinned to a column
+    let error = input::parse_lines::<_, Vec<_>>("add x\n", word).unwrap_err();
+    assert_eq!(error.column, None);
+    assert!(error.to_string().starts_with("line 1: bad number"));
+}
@@ -0,0 +1,116 @@
+//! Setting puzzle parameters from the params file and the command line.
+
+use aoc2022::{
+    days::{self, day15::Day15},
+    params::{self, Params, ParamsEntry},
+};
+use serde_json::json;
+
+fn params(value: serde_json::Value) -> Params {
+    value.as_object().unwrap().clone()
+}
+
+#[test]
+fn later_entries_win() {
+    let entries = vec![
+        ParamsEntry {
+            day: 15,
+            input: Some("day15-sample.txt".to_string()),
+            params: params(json!({ "target_row": 10 })),
+        },
+        ParamsEntry {
+            day: 15,
+            input: None,
+            params: params(json!({ "target_row": 1, "max_search": 20 })),
+        },
+        ParamsEntry {
+            day: 16,
+            input: None,
+            params: params(json!({ "part1_minutes": 5 })),
+        },
+    ];
+
+    // The whole-day entry, then the one for this file, then the overrides
+    let overrides = params(json!({ "max_search": 30 }));
+    assert_eq!(
+        params::lookup(&entries, 15, "./data/day15-sample.txt", &overrides),
+        params(json!({ "target_row": 10, "max_search": 30 }))
+    );
+    assert_eq!(
+        params::lookup(&entries, 15, "./data/day15.txt", &Params::new()),
+        params(json!({ "target_row": 1, "max_search": 20 }))
+    );
+}
+
+#[test]
+fn inputs_are_matched_by_their_path_in_the_data_directory() {
+    let entries = vec![
+        ParamsEntry {
+            day: 15,
+            input: Some("alice/day15.txt".to_string()),
+            params: params(json!({ "target_row": 1 })),
+        },
+        ParamsEntry {
+            day: 15,
+            input: Some("bob/day15.txt".to_string()),
+            params: params(json!({ "target_row": 2 })),
+        },
+    ];
+    let target_row = |input_path| {
+        params::lookup(&entries, 15, input_path, &Params::new())
+            .get("target_row")
+            .cloned()
+    };
+
+    // Both profiles' inputs are called day15.txt, so the file name alone
+    // can't tell them apart
+    assert_eq!(target_row("./data/alice/day15.txt"), Some(json!(1)));
+    assert_eq!(target_row("data/bob/day15.txt"), Some(json!(2)));
+    assert_eq!(target_row("bob/day15.txt"), Some(json!(2)));
+    assert_eq!(target_row("./data/day15.txt"), None);
+    assert_eq!(target_row("/elsewhere/alice/day15.txt"), None);
+}
+
+#[test]
+fn configured_solver_uses_the_params() {
+    let sample = params::load(params::PARAMS_PATH).unwrap();
+    let solution = days::configured(
+        15,
+        &params::lookup(&sample, 15, "day15-sample.txt", &Params::new()),
+    )
+    .unwrap();
+    let parsed = solution
+        .parse(include_str!("../data/day15-sample.txt"))
+        .unwrap();
+    assert_eq!(solution.part1(&*parsed).unwrap(), "26");
+    assert_eq!(solution.part2(&*parsed).unwrap(), "56000011");
+
+    let solver = params::configure(&Day15::REAL, &params(json!({ "max_search": 20 }))).unwrap();
+    assert_eq!(
+        (solver.target_row, solver.max_search),
+        (Day15::REAL.target_row, 20)
+    );
+}
+
+#[test]
+fn bad_params_are_errors() {
+    let unknown = days::configured(7, &params(json!({ "disk": 1 })));
+    assert!(unknown.is_err());
+    let none_taken = days::configured(1, &params(json!({ "disk": 1 })));
+    assert!(none_taken.is_err());
+    let wrong_type = days::configured(20, &params(json!({ "mix_rounds": "ten" })));
+    assert!(wrong_type.is_err());
+}
+
+#[test]
+fn overrides_parse_as_json_or_strings() {
+    assert_eq!(
+        params::parse_override("max_search=20").unwrap(),
+        ("max_search".to_string(), json!(20))
+    );
+    assert_eq!(
+        params::parse_override("name=a=b").unwrap(),
+        ("name".to_string(), json!("a=b"))
+    );
+    assert!(params::parse_override("max_search").is_err());
+}
@@ -0,0 +1,92 @@
+//! Reading typed structs out of lines with `line_pattern!`, and the errors
+//! for lines that don't fit.
+
+use aoc2022::{days, error::InputError, input::parse_lines, line_pattern, pattern::List};
+
+line_pattern! {
+    #[derive(Debug, PartialEq)]
+    struct Valve = r"Valve (\w+) has flow rate=(-?\d+); tunnels? leads? to valves? (.+)" {
+        name: String,
+        flow_rate: i32,
+        tunnels: List<String>,
+    }
+}
+
+fn parse_error(day: usize, input: &str) -> String {
+    let solution = days::get(day).unwrap();
+    match solution.parse(input) {
+        Ok(_) => panic!("day {} parsed {:?}", day, input),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[test]
+fn fields_are_converted_in_order() {
+    assert_eq!(
+        Valve::parse_line("Valve AA has flow rate=-3; tunnels lead to valves DD, II, BB").unwrap(),
+        Valve {
+            name: "AA".to_string(),
+            flow_rate: -3,
+            tunnels: List(vec!["DD".to_string(), "II".to_string(), "BB".to_string()]),
+        }
+    );
+}
+
+#[test]
+fn mismatches_show_the_pattern_and_the_line() {
+    let input = "Valve AA has flow rate=0; tunnel leads to valve BB\nValve BB is stuck\n";
+    let error: InputError = parse_lines::<_, Vec<_>>(input, Valve::parse_line).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 2: expected a line matching \
+         /Valve (\\w+) has flow rate=(-?\\d+); tunnels? leads? to valves? (.+)/ \
+         in \"Valve BB is stuck\""
+    );
+
+    // The whole line has to match, not just some of it
+    assert!(Valve::parse_line("The Valve AA has flow rate=0; tunnel leads to valve BB").is_err());
+}
+
+#[test]
+fn bad_fields_are_named() {
+    let error = Valve::parse_line("Valve AA has flow rate=99999999999; tunnel leads to valve BB")
+        .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("bad flow_rate \"99999999999\""),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn days_report_the_pattern() {
+    let error = parse_error(4, "2-4,6-8\n2-4;6-8\n");
+    assert_eq!(
+        error,
+        "line 2: expected a line matching /(\\d+)-(\\d+),(\\d+)-(\\d+)/ in \"2-4;6-8\""
+    );
+
+    let moves =
+        include_str!("../data/day5-sample.txt").replace("move 1 from 2 to 1", "move 1 from 2");
+    assert!(parse_error(5, &moves).contains("/move (\\d+) from (\\d+) to (\\d+)/"));
+
+    let error = parse_error(16, "Valve AA has flow rate=0; tunnel leads to valve B\n");
+    assert!(error.contains("valve names are two letters"), "{}", error);
+
+    let error = parse_error(19, "Blueprint 1: Each ore robot costs 4 ore.\n");
+    assert!(error.starts_with("line 1: expected a line matching /Blueprint"));
+
+    let error = parse_error(
+        21,
+        "root: pppw + sjmn\npppw: cczh / lfqf\nhumn: 5\nsjmn: 2 x\n",
+    );
+    assert!(error.starts_with("line 4: expected a line matching /(\\w+): (\\w+) (.) (\\w+)/"));
+    let error = parse_error(21, "root: pppw % sjmn\n");
+    assert!(
+        error.starts_with("line 1, column 12: unknown token \"%\""),
+        "{}",
+        error
+    );
+}
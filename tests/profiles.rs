@@ -0,0 +1,81 @@
+//! Keeping several people's inputs side by side, in profile directories.
+
+use std::{fs, path::PathBuf, process};
+
+use aoc2022::{params::Params, profile::Profile, verify};
+
+/// A data directory of its own, holding a named profile whose inputs are the
+/// worked examples, so the answers are known.
+fn example_root() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc-profiles-{}", process::id()));
+    let alice = root.join("alice");
+    fs::create_dir_all(&alice).unwrap();
+    fs::write(
+        alice.join("day1.txt"),
+        include_str!("../data/day1-sample.txt"),
+    )
+    .unwrap();
+    fs::write(
+        alice.join("day2.txt"),
+        include_str!("../data/day2-sample.txt"),
+    )
+    .unwrap();
+    fs::write(
+        alice.join("answers.json"),
+        r#"[
+            { "day": 1, "input": "day1.txt", "part1": "24000", "part2": "45000" },
+            { "day": 2, "input": "day2.txt", "part1": "15", "part2": "12" }
+        ]"#,
+    )
+    .unwrap();
+    // Not a profile, as it has no answers
+    fs::create_dir_all(root.join("scratch")).unwrap();
+    root
+}
+
+#[test]
+fn named_profiles_are_checked_against_their_own_answers() {
+    let root = example_root();
+    let profiles = Profile::all_in(&root).unwrap();
+    let names: Vec<&str> = profiles.iter().map(|p| p.name()).collect();
+    assert_eq!(names, ["default", "alice"]);
+
+    let alice = &profiles[1];
+    assert_eq!(
+        PathBuf::from(alice.input_path(1)),
+        root.join("alice").join("day1.txt")
+    );
+
+    let expected = alice.load_answers().unwrap();
+    assert_eq!(expected[0].input, "alice/day1.txt");
+    for e in &expected {
+        for check in verify::check(e, &Params::new()) {
+            assert!(check.outcome.is_pass(), "{:?}", check);
+        }
+    }
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn missing_and_bad_profiles_are_errors() {
+    assert!(Profile::named("no-such-profile").is_err());
+    assert!(Profile::named("../data").is_err());
+    assert!(Profile::named("").is_err());
+}
+
+/// Every profile in the data directory names inputs that are really there,
+/// so that `aoc --verify --all-profiles` checks them all.
+#[test]
+fn every_profile_has_its_inputs() {
+    for profile in Profile::all().unwrap() {
+        for expected in profile.load_answers().unwrap() {
+            assert!(
+                expected.input_path().is_file(),
+                "profile {} is missing {}",
+                profile.name(),
+                expected.input_path().display()
+            );
+        }
+    }
+}
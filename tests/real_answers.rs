@@ -0,0 +1,77 @@
+//! Checks the days that used to need nightly-only features against their
+//! known answers for the real inputs, on top of the worked examples in
+//! `samples.rs`. The slowest parts are ignored by default; run them with
+//! `cargo test --release -- --ignored`.
+//!
+//! The answers are written out here rather than read from `answers.json`, as
+//! that file was filled in by running this code. Each was confirmed apart from
+//! it: day 15 part 2 and day 16 part 2 by the original nightly solvers, and
+//! the rest by separate brute-force solutions.
+
+use std::path::PathBuf;
+
+use aoc2022::{answers::Expected, params::Params, profile::DATA_DIR, verify};
+
+/// The real answers, as (day, part 1, part 2).
+const ANSWERS: &[(usize, &str, &str)] = &[
+    (15, "5127797", "12518502636475"),
+    (16, "1986", "2464"),
+    (17, "3055", "1507692307690"),
+];
+
+/// Checks one part of `day` on its real input against `ANSWERS`.
+fn check_real(day: usize, part: usize) {
+    let &(_, part1, part2) = ANSWERS
+        .iter()
+        .find(|&&(d, _, _)| d == day)
+        .unwrap_or_else(|| panic!("no answers for day {}", day));
+    let expected = Expected {
+        day,
+        input: format!("day{}.txt", day),
+        part1: (part == 1).then(|| part1.to_string()),
+        part2: (part == 2).then(|| part2.to_string()),
+        dir: PathBuf::from(DATA_DIR),
+    };
+
+    for check in verify::check(&expected, &Params::new()) {
+        assert!(
+            check.outcome.is_pass(),
+            "day {} part {}: {:?}",
+            day,
+            part,
+            check.outcome
+        );
+    }
+}
+
+#[test]
+fn day15_part1() {
+    check_real(15, 1);
+}
+
+#[test]
+#[ignore = "slow"]
+fn day15_part2() {
+    check_real(15, 2);
+}
+
+#[test]
+fn day16_part1() {
+    check_real(16, 1);
+}
+
+#[test]
+#[ignore = "slow"]
+fn day16_part2() {
+    check_real(16, 2);
+}
+
+#[test]
+fn day17_part1() {
+    check_real(17, 1);
+}
+
+#[test]
+fn day17_part2() {
+    check_real(17, 2);
+}
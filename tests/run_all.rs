@@ -0,0 +1,96 @@
+//! Runs a few days at once, one of which panics and one of which never
+//! finishes, to check that neither takes the others down.
+
+use std::{error, thread, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use aoc2022::{
+    days::day3::Day3,
+    error::InputError,
+    profile::Profile,
+    run_all::{self, Status},
+    solution::{AnySolution, Solution},
+};
+
+/// Stands in for day 1, panicking in part 2.
+#[derive(Serialize, Deserialize)]
+struct Panics;
+
+impl Solution for Panics {
+    const DAY: usize = 1;
+
+    type Parsed = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, _input: &str) -> Result<Self::Parsed, InputError> {
+        Ok(())
+    }
+
+    fn part1(&self, _parsed: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(1)
+    }
+
+    fn part2(&self, _parsed: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        panic!("part 2 blew up")
+    }
+}
+
+/// Stands in for day 2, taking far longer than the timeout.
+#[derive(Serialize, Deserialize)]
+struct Hangs;
+
+impl Solution for Hangs {
+    const DAY: usize = 2;
+
+    type Parsed = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, _input: &str) -> Result<Self::Parsed, InputError> {
+        thread::sleep(Duration::from_secs(60));
+        Ok(())
+    }
+
+    fn part1(&self, _parsed: &Self::Parsed) -> Result<Self::Part1, Box<dyn error::Error>> {
+        Ok(0)
+    }
+
+    fn part2(&self, _parsed: &Self::Parsed) -> Result<Self::Part2, Box<dyn error::Error>> {
+        Ok(0)
+    }
+}
+
+#[test]
+fn failures_are_reported_without_stopping_the_others() {
+    // Enough workers that every day gets one, however many cores there are
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build_global()
+        .unwrap();
+
+    let solutions: Vec<Box<dyn AnySolution>> =
+        vec![Box::new(Panics), Box::new(Hangs), Box::new(Day3)];
+    let report = run_all::run(solutions, &Profile::default(), Duration::from_secs(2));
+
+    let statuses: Vec<(usize, usize, Status)> = report
+        .runs
+        .iter()
+        .map(|r| (r.day, r.part, r.status.clone()))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            (1, 1, Status::Ok),
+            (1, 2, Status::Panic("part 2 blew up".to_string())),
+            (2, 1, Status::Timeout),
+            (2, 2, Status::Timeout),
+            (3, 1, Status::Ok),
+            (3, 2, Status::Ok),
+        ]
+    );
+    assert_eq!(report.runs[0].answer.as_deref(), Some("1"));
+    assert_eq!(report.runs[4].answer.as_deref(), Some("8252"));
+    assert_eq!((report.ok, report.failed), (3, 3));
+}
@@ -0,0 +1,199 @@
+//! Runs every day against the worked example from its puzzle description.
+
+use aoc2022::{
+    days::{self, day15::Day15},
+    solution::AnySolution,
+};
+
+fn check_part(solution: &dyn AnySolution, input: &str, part: usize, expected: &str) {
+    let day = solution.day();
+    let parsed = solution
+        .parse(input)
+        .unwrap_or_else(|e| panic!("day {} part {}: parse failed: {}", day, part, e));
+    let actual = solution
+        .part(part, &*parsed)
+        .unwrap_or_else(|e| panic!("day {} part {}: {}", day, part, e));
+    assert_eq!(actual, expected, "day {} part {}", day, part);
+}
+
+fn check(solution: &dyn AnySolution, input: &str, part1: &str, part2: &str) {
+    check_part(solution, input, 1, part1);
+    check_part(solution, input, 2, part2);
+}
+
+fn check_day(day: usize, input: &str, part1: &str, part2: &str) {
+    check(days::get(day).unwrap(), input, part1, part2);
+}
+
+#[test]
+fn day1() {
+    check_day(1, include_str!("../data/day1-sample.txt"), "24000", "45000");
+}
+
+#[test]
+fn day2() {
+    check_day(2, include_str!("../data/day2-sample.txt"), "15", "12");
+}
+
+#[test]
+fn day3() {
+    check_day(3, include_str!("../data/day3-sample.txt"), "157", "70");
+}
+
+#[test]
+fn day4() {
+    check_day(4, include_str!("../data/day4-sample.txt"), "2", "4");
+}
+
+#[test]
+fn day5() {
+    check_day(5, include_str!("../data/day5-sample.txt"), "CMZ", "MCD");
+}
+
+#[test]
+fn day6() {
+    check_day(6, include_str!("../data/day6-sample.txt"), "7", "19");
+}
+
+#[test]
+fn day7() {
+    check_day(
+        7,
+        include_str!("../data/day7-sample.txt"),
+        "95437",
+        "24933642",
+    );
+}
+
+#[test]
+fn day8() {
+    check_day(8, include_str!("../data/day8-sample.txt"), "21", "8");
+}
+
+#[test]
+fn day9() {
+    check_day(9, include_str!("../data/day9-sample.txt"), "13", "1");
+    check_part(
+        days::get(9).unwrap(),
+        include_str!("../data/day9-sample-large.txt"),
+        2,
+        "36",
+    );
+}
+
+#[test]
+fn day10() {
+    let screen = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+    check_day(
+        10,
+        include_str!("../data/day10-sample.txt"),
+        "13140",
+        screen,
+    );
+}
+
+#[test]
+fn day11() {
+    check_day(
+        11,
+        include_str!("../data/day11-sample.txt"),
+        "10605",
+        "2713310158",
+    );
+}
+
+#[test]
+fn day12() {
+    check_day(12, include_str!("../data/day12-sample.txt"), "31", "29");
+}
+
+#[test]
+fn day13() {
+    check_day(13, include_str!("../data/day13-sample.txt"), "13", "140");
+}
+
+#[test]
+fn day14() {
+    check_day(14, include_str!("../data/day14-sample.txt"), "24", "93");
+}
+
+#[test]
+fn day15() {
+    check(
+        &Day15::SAMPLE,
+        include_str!("../data/day15-sample.txt"),
+        "26",
+        "56000011",
+    );
+}
+
+#[test]
+fn day16() {
+    check_day(16, include_str!("../data/day16-sample.txt"), "1651", "1707");
+}
+
+#[test]
+fn day17() {
+    check_day(
+        17,
+        include_str!("../data/day17-sample.txt"),
+        "3068",
+        "1514285714288",
+    );
+}
+
+#[test]
+fn day18() {
+    check_day(18, include_str!("../data/day18-sample.txt"), "64", "58");
+}
+
+#[test]
+fn day19() {
+    check_day(19, include_str!("../data/day19-sample.txt"), "33", "3472");
+}
+
+#[test]
+fn day20() {
+    check_day(
+        20,
+        include_str!("../data/day20-sample.txt"),
+        "3",
+        "1623178306",
+    );
+}
+
+#[test]
+fn day21() {
+    check_day(21, include_str!("../data/day21-sample.txt"), "152", "301");
+}
+
+#[test]
+fn day22() {
+    check_day(22, include_str!("../data/day22-sample.txt"), "6032", "5031");
+}
+
+#[test]
+fn day23() {
+    check_day(23, include_str!("../data/day23-sample.txt"), "110", "20");
+}
+
+#[test]
+fn day24() {
+    check_day(24, include_str!("../data/day24-sample.txt"), "18", "54");
+}
+
+#[test]
+fn day25() {
+    check_day(
+        25,
+        include_str!("../data/day25-sample.txt"),
+        "2=-1=0",
+        "Merry Christmas!",
+    );
+}
@@ -0,0 +1,173 @@
+//! Steps through simulations with the viewer, checking that stepping back and
+//! jumping around land on the same frames as stepping forward, and that the
+//! days' simulations end where their answers say they should.
+
+use aoc2022::{
+    days,
+    viewer::{Simulation, Viewer, MAX_ZOOM, RUN_LIMIT},
+};
+
+/// Counts up to `end`, drawing the count as a row of `#`s.
+#[derive(Clone)]
+struct Counter {
+    count: usize,
+    end: usize,
+}
+
+impl Simulation for Counter {
+    fn step(&mut self) -> bool {
+        if self.count == self.end {
+            return false;
+        }
+        self.count += 1;
+        true
+    }
+
+    fn status(&self) -> String {
+        format!("count {}", self.count)
+    }
+
+    fn rows(&self) -> Vec<String> {
+        vec![".".repeat(self.end - self.count) + &"#".repeat(self.count)]
+    }
+}
+
+fn counter(end: usize) -> Viewer {
+    Viewer::new(Box::new(Counter { count: 0, end }))
+}
+
+fn day_viewer(day: usize, input: &str, part: usize) -> Viewer {
+    let solution = days::get(day).unwrap();
+    let parsed = solution.parse(input).unwrap();
+    Viewer::new(solution.simulation(&*parsed, part).unwrap())
+}
+
+/// Runs `viewer` to the end, returning the last step.
+fn run_to_end(viewer: &mut Viewer) -> usize {
+    while viewer.forward() {}
+    viewer.last().unwrap()
+}
+
+#[test]
+fn stepping_back_and_jumping() {
+    let mut viewer = counter(1000);
+    assert_eq!(viewer.goto(750), 750);
+    assert_eq!(viewer.status(), "count 750");
+
+    // Back past a checkpoint, then forward again
+    for step in (150..750).rev() {
+        assert!(viewer.back());
+        assert_eq!(viewer.status(), format!("count {}", step));
+    }
+    assert_eq!(viewer.goto(420), 420);
+    assert_eq!(viewer.status(), "count 420");
+
+    assert_eq!(viewer.goto(5000), 1000);
+    assert_eq!(viewer.last(), Some(1000));
+    assert!(!viewer.forward());
+    assert_eq!(viewer.goto(0), 0);
+    assert!(!viewer.back());
+}
+
+#[test]
+fn long_jumps_stop_at_the_run_limit() {
+    let mut viewer = counter(3 * RUN_LIMIT);
+    assert_eq!(viewer.goto(usize::MAX), RUN_LIMIT);
+    assert_eq!(viewer.last(), None);
+    assert_eq!(viewer.goto(5 * RUN_LIMIT / 2), 2 * RUN_LIMIT);
+    assert_eq!(viewer.goto(5 * RUN_LIMIT / 2), 5 * RUN_LIMIT / 2);
+}
+
+#[test]
+fn breakpoints() {
+    let mut viewer = counter(500);
+    assert!(viewer.run_until("count 3"));
+    assert_eq!(viewer.step(), 3);
+    // The current step doesn't count, so this finds the next match
+    assert!(viewer.run_until("count 3"));
+    assert_eq!(viewer.step(), 30);
+    assert!(!viewer.run_until("count 1000"));
+    assert_eq!(viewer.step(), 500);
+}
+
+#[test]
+fn zoom_and_pan() {
+    let mut viewer = counter(8);
+    viewer.goto(3);
+    assert_eq!(viewer.view(80, 10), vec![".....###"]);
+
+    viewer.zoom_out();
+    assert_eq!(viewer.view(80, 10), vec!["..##"]);
+    viewer.zoom_out();
+    assert_eq!(viewer.view(80, 10), vec![".#"]);
+    viewer.pan_by(1, 0);
+    assert_eq!(viewer.pan(), (4, 0));
+    assert_eq!(viewer.view(80, 10), vec!["#"]);
+
+    viewer.zoom_in();
+    viewer.pan_by(-10, 0);
+    assert_eq!(viewer.pan(), (0, 0));
+    assert_eq!(viewer.view(3, 10), vec!["..#"]);
+
+    for _ in 0..100 {
+        viewer.zoom_out();
+    }
+    assert_eq!(viewer.zoom(), MAX_ZOOM);
+    assert_eq!(viewer.view(80, 10), vec!["#"]);
+}
+
+#[test]
+fn day9_moves_the_head_one_step_at_a_time() {
+    let input = include_str!("../data/day9-sample.txt");
+    let mut viewer = day_viewer(9, input, 1);
+    assert_eq!(run_to_end(&mut viewer), 24);
+    assert!(viewer
+        .status()
+        .ends_with("13 positions visited by the tail"));
+}
+
+#[test]
+fn day14_pours_one_unit_per_step() {
+    let input = include_str!("../data/day14-sample.txt");
+    let mut viewer = day_viewer(14, input, 1);
+    assert_eq!(run_to_end(&mut viewer), 24);
+    assert_eq!(viewer.status(), "24 units of sand at rest");
+    assert!(viewer.rows().iter().any(|row| row.contains('o')));
+    assert_eq!(run_to_end(&mut day_viewer(14, input, 2)), 93);
+}
+
+#[test]
+fn day17_drops_one_rock_per_step() {
+    let input = include_str!("../data/day17-sample.txt");
+    let mut viewer = day_viewer(17, input, 1);
+    assert_eq!(run_to_end(&mut viewer), 2022);
+    assert!(viewer.status().ends_with("tower height 3068"));
+}
+
+#[test]
+fn day22_follows_one_instruction_per_step() {
+    let input = include_str!("../data/day22-sample.txt");
+    let mut viewer = day_viewer(22, input, 1);
+    run_to_end(&mut viewer);
+    assert!(viewer.status().ends_with("password 6032"));
+    let mut viewer = day_viewer(22, input, 2);
+    run_to_end(&mut viewer);
+    assert!(viewer.status().ends_with("password 5031"));
+}
+
+#[test]
+fn day23_runs_one_round_per_step() {
+    let input = include_str!("../data/day23-sample.txt");
+    let mut viewer = day_viewer(23, input, 1);
+    assert_eq!(run_to_end(&mut viewer), 10);
+    assert_eq!(viewer.status(), "10 rounds, 110 empty ground tiles");
+    // The round in which no elf moves is the answer to part 2
+    assert_eq!(run_to_end(&mut day_viewer(23, input, 2)) + 1, 20);
+}
+
+#[test]
+fn days_without_a_simulation() {
+    let solution = days::get(1).unwrap();
+    let parsed = solution.parse("1\n").unwrap();
+    assert!(solution.simulation(&*parsed, 1).is_err());
+}
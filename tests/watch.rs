@@ -0,0 +1,134 @@
+//! Noticing changes to inputs and sources for --watch, and the table that
+//! compares each run's answers with the last.
+#![cfg(target_os = "linux")]
+
+use std::{env, fs, process, time::Duration};
+
+use aoc2022::{
+    report::{PhaseReport, SolveReport},
+    watch::{self, Answers, Watcher},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn phase(part: Option<usize>, answer: Option<&str>, error: Option<&str>) -> PhaseReport {
+    PhaseReport {
+        part,
+        answer: answer.map(str::to_string),
+        error: error.map(str::to_string),
+        elapsed_ns: 0,
+        diagnostics: Default::default(),
+    }
+}
+
+fn answer(
+    input: &str,
+    part: usize,
+    answer: Result<&str, &str>,
+) -> ((String, usize), Result<String, String>) {
+    (
+        (input.to_string(), part),
+        answer.map(str::to_string).map_err(str::to_string),
+    )
+}
+
+#[test]
+fn watcher_sees_writes_and_renames() {
+    let dir = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+    let src = dir.join("src");
+    fs::create_dir_all(&src).unwrap();
+    let input = dir.join("day1.txt");
+    fs::write(&input, "1\n").unwrap();
+
+    let mut watcher =
+        Watcher::new(std::slice::from_ref(&input), std::slice::from_ref(&src)).unwrap();
+    assert!(watcher
+        .wait(Some(Duration::from_millis(100)))
+        .unwrap()
+        .is_empty());
+
+    fs::write(&input, "2\n").unwrap();
+    assert_eq!(watcher.wait(Some(TIMEOUT)).unwrap(), vec![input.clone()]);
+
+    // Other files next to the input don't count, but any source file does,
+    // even when it's saved by renaming a new file over it
+    fs::write(dir.join("day2.txt"), "3\n").unwrap();
+    fs::write(src.join("notes.txt"), "nothing\n").unwrap();
+    fs::write(src.join("day1.rs.new"), "fn main() {}\n").unwrap();
+    fs::rename(src.join("day1.rs.new"), src.join("day1.rs")).unwrap();
+    assert_eq!(
+        watcher.wait(Some(TIMEOUT)).unwrap(),
+        vec![src.join("day1.rs")]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn answers_from_a_report() {
+    let report = SolveReport {
+        day: 1,
+        input: "data/day1.txt".to_string(),
+        parse: phase(None, None, None),
+        parts: vec![
+            phase(Some(1), Some("24000"), None),
+            phase(Some(2), None, Some("no elves")),
+        ],
+    };
+    let expected: Answers = vec![
+        answer("data/day1.txt", 1, Ok("24000")),
+        answer("data/day1.txt", 2, Err("no elves")),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(watch::answers(&report, &[1, 2]), expected);
+
+    // Parsing failing is an error for every part asked for
+    let report = SolveReport {
+        parse: phase(None, None, Some("line 3: bad number")),
+        parts: Vec::new(),
+        ..report
+    };
+    let expected: Answers = vec![answer("data/day1.txt", 2, Err("line 3: bad number"))]
+        .into_iter()
+        .collect();
+    assert_eq!(watch::answers(&report, &[2]), expected);
+}
+
+#[test]
+fn comparison_with_the_last_run() {
+    let inputs = vec!["data/day10.txt".to_string(), "sample".to_string()];
+    let previous: Answers = vec![
+        answer("data/day10.txt", 1, Ok("13140")),
+        answer("data/day10.txt", 2, Ok("##..\n..##")),
+        answer("sample", 1, Ok("7")),
+    ]
+    .into_iter()
+    .collect();
+    let current: Answers = vec![
+        answer("data/day10.txt", 1, Ok("13140")),
+        answer("data/day10.txt", 2, Ok("#...\n...#")),
+        answer("sample", 1, Ok("8")),
+        answer("sample", 2, Err("no answer")),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        watch::compare(&inputs, &current, &previous),
+        "input           part  answer
+data/day10.txt     1  13140 (unchanged)
+data/day10.txt     2  (changed)
+#...
+...#
+sample             1  8 (was 7)
+sample             2  error: no answer
+"
+    );
+
+    // The first run has nothing to compare with
+    assert_eq!(
+        watch::compare(&inputs[1..], &current, &Answers::new()),
+        "input   part  answer\nsample     1  8\nsample     2  error: no answer\n"
+    );
+}